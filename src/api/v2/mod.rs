@@ -2,10 +2,12 @@ mod model;
 
 use std::collections::HashMap;
 use std::ops::Deref;
+use std::sync::Arc;
+use tokio::sync::RwLock;
 use rocket::{Route, State};
 use rocket::http::Status;
 use rocket::serde::json::Json;
-use crate::api::v1::model::Forecasts;
+use crate::api::v2::model::Forecasts;
 use crate::providers::Winds;
 
 pub(crate) fn routes() -> Vec<Route> {
@@ -13,8 +15,8 @@ pub(crate) fn routes() -> Vec<Route> {
 }
 
 #[get("/winds?<provider>")]
-async fn get(winds: &State<HashMap<String, Winds>>, provider: String) -> Result<Json<Forecasts>, Status> {
-    match winds.get(&provider) {
+async fn get(providers: &State<Arc<RwLock<HashMap<String, Winds>>>>, provider: String) -> Result<Json<Forecasts>, Status> {
+    match providers.read().await.get(&provider) {
         Some(winds) => {
             let forecasts: Forecasts = winds.read().await.deref().into();
             Ok(Json(forecasts))
@@ -1,11 +1,12 @@
 use std::fmt::{Display, Formatter};
 use std::fs;
 use std::fs::File;
-use std::io::{BufReader, Read};
-use std::path::Path;
+use std::io::{BufRead, BufReader, Read};
+use std::path::{Path, PathBuf};
 use anyhow::anyhow;
 use chrono::{DateTime, Utc};
 use flate2::Compression;
+use tempfile::NamedTempFile;
 use s3::Bucket;
 use serde::{Serialize, Deserialize};
 use crate::providers::json::Message;
@@ -15,9 +16,13 @@ use crate::stamp::Stamp;
 #[serde(rename_all = "camelCase")]
 pub struct Config {
   pub providers: Vec<ProviderConfig>,
+  /// Path to the embedded key-value store that persists decoded grids across
+  /// restarts. Left unset, the process runs with an in-memory cache only.
+  #[serde(default)]
+  pub store: Option<String>,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(rename_all = "camelCase")]
 pub enum ProviderConfig {
   Noaa(NoaaProviderConfig),
@@ -25,31 +30,85 @@ pub enum ProviderConfig {
   Zezo(ZezoProviderConfig),
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+impl ProviderConfig {
+  /// Stable identifier used as the key in the live-provider registry; matches
+  /// `Status::provider` so the two maps line up.
+  pub fn key(&self) -> &'static str {
+    match self {
+      ProviderConfig::Noaa(_) => "noaa",
+      ProviderConfig::Meteofrance(_) => "meteofrance",
+      ProviderConfig::Zezo(_) => "zezo",
+    }
+  }
+
+  pub fn enabled(&self) -> bool {
+    match self {
+      ProviderConfig::Noaa(c) => c.enabled,
+      ProviderConfig::Meteofrance(c) => c.enabled,
+      ProviderConfig::Zezo(c) => c.enabled,
+    }
+  }
+}
+
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct NoaaProviderConfig {
   pub enabled: bool,
   pub init: Option<DateTime<Utc>>,
   pub jsons: Storage,
+  /// Decode GRIB natively instead of shelling out to the bundled `grib2json`.
+  #[serde(default)]
+  pub native: bool,
+  #[serde(default)]
+  pub retry: RetryConfig,
 }
 
-#[derive(Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 pub struct ZezoProviderConfig {
   pub enabled: bool,
   pub init: Option<DateTime<Utc>>,
   pub pngs: Storage,
+  #[serde(default)]
+  pub retry: RetryConfig,
 }
 
-#[derive(Default, Debug, Serialize, Deserialize)]
+/// Retry policy for transient download/convert failures. `maxAttempts` counts
+/// the total tries; delays grow exponentially from `baseDelaySecs`, capped at
+/// `maxDelaySecs`.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct RetryConfig {
+  pub max_attempts: u32,
+  pub base_delay_secs: u64,
+  pub max_delay_secs: u64,
+}
+
+impl Default for RetryConfig {
+  fn default() -> Self {
+    Self {
+      max_attempts: 5,
+      base_delay_secs: 1,
+      max_delay_secs: 60,
+    }
+  }
+}
+
+#[derive(Clone, Default, Debug, PartialEq, Serialize, Deserialize)]
 pub struct MeteofranceProviderConfig {
   pub(crate) enabled: bool,
   token: String,
 }
 
-#[derive(Clone, Debug, Serialize, Deserialize)]
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
 #[serde(untagged)]
 pub enum Storage {
   Local{
-    dir: String
+    dir: String,
+    #[serde(default)]
+    compression: Option<Zstd>,
+    /// When set, payloads larger than `part_size` are stored as numbered parts
+    /// plus a manifest; smaller ones stay single-object.
+    #[serde(default)]
+    split: Option<Split>,
   },
   ObjectStorage {
     endpoint: String,
@@ -57,13 +116,224 @@ pub enum Storage {
     bucket: String,
     access_key: String,
     secret_key: String,
+    /// Optional key prefix so several providers (or deployments) can share one
+    /// bucket without colliding; empty means keys live at the bucket root.
+    #[serde(default)]
+    prefix: String,
+    /// Codec applied to uploaded objects; defaults to gzip for backwards
+    /// compatibility with archives written before this was configurable.
+    #[serde(default)]
+    compression: ObjectCompression,
+    /// When set, payloads larger than `part_size` are stored as numbered parts
+    /// plus a manifest; smaller ones stay single-object.
+    #[serde(default)]
+    split: Option<Split>,
+  }
+}
+
+/// Multi-part object configuration: payloads above `part_size` bytes are
+/// chunked into `name.part0`, `name.part1`, … siblings alongside a
+/// `name.manifest` describing the parts and the overall digest. Splitting keeps
+/// individual objects under single-object size limits and lets the object-store
+/// backend transfer parts concurrently.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Split {
+  /// Maximum size of a single part, in bytes.
+  pub part_size: u64,
+}
+
+/// One entry of a split object's [`Manifest`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct ManifestPart {
+  name: String,
+  size: u64,
+}
+
+/// Source-of-truth description of a split object: the ordered part list and the
+/// SHA-256 of the reassembled payload. A missing or size-mismatched part is an
+/// error rather than a truncated deserialization.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+struct Manifest {
+  parts: Vec<ManifestPart>,
+  digest: String,
+}
+
+/// Compression codec for objects written through [`Storage::ObjectStorage`].
+///
+/// The selected codec drives both the encoder used by [`Storage::save`] and the
+/// `content-encoding` header it sets, and the matching decoder on the read
+/// path. Zstd trades far better ratio-per-CPU than gzip on the large gridded
+/// JSON this crate emits, so operators can pick it to cut upload latency.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub enum ObjectCompression {
+  None,
+  Gzip { level: u32 },
+  Zstd { level: i32 },
+}
+
+impl Default for ObjectCompression {
+  /// Gzip at the previous hard-coded best level, so existing deployments keep
+  /// producing byte-identical `content-encoding: gzip` objects.
+  fn default() -> Self {
+    ObjectCompression::Gzip { level: 9 }
+  }
+}
+
+impl ObjectCompression {
+  /// The `content-encoding` header value for this codec, or `None` when the
+  /// payload is stored uncompressed.
+  fn content_encoding(&self) -> Option<&'static str> {
+    match self {
+      ObjectCompression::None => None,
+      ObjectCompression::Gzip { .. } => Some("gzip"),
+      ObjectCompression::Zstd { .. } => Some("zstd"),
+    }
+  }
+
+  /// Encode `from` into the in-memory buffer uploaded to the object store.
+  fn encode<P: AsRef<Path>>(&self, from: P) -> anyhow::Result<Vec<u8>> {
+    let file = File::open(from)?;
+    match self {
+      ObjectCompression::None => {
+        let mut buffer = Vec::new();
+        BufReader::new(file).read_to_end(&mut buffer)?;
+        Ok(buffer)
+      }
+      ObjectCompression::Gzip { level } => {
+        let mut gz = flate2::bufread::GzEncoder::new(BufReader::new(file), Compression::new(*level));
+        let mut buffer = Vec::new();
+        gz.read_to_end(&mut buffer)?;
+        Ok(buffer)
+      }
+      ObjectCompression::Zstd { level } => {
+        Ok(zstd::stream::encode_all(BufReader::new(file), *level)?)
+      }
+    }
+  }
+
+  /// Streaming counterpart to [`decode`](Self::decode): wrap `reader` in the
+  /// matching decoder so a caller can pull decoded bytes incrementally instead
+  /// of materialising the whole object in memory first.
+  fn decode_stream<'a, R: BufRead + 'a>(&self, reader: R) -> anyhow::Result<Box<dyn Read + 'a>> {
+    Ok(match self {
+      ObjectCompression::None => Box::new(reader),
+      ObjectCompression::Gzip { .. } => Box::new(flate2::bufread::GzDecoder::new(reader)),
+      ObjectCompression::Zstd { .. } => Box::new(zstd::stream::read::Decoder::with_buffer(reader)?),
+    })
+  }
+
+  /// Decode object bytes previously written with this codec.
+  fn decode(&self, bytes: Vec<u8>) -> anyhow::Result<Vec<u8>> {
+    match self {
+      ObjectCompression::None => Ok(bytes),
+      ObjectCompression::Gzip { .. } => {
+        let mut out = Vec::new();
+        flate2::bufread::GzDecoder::new(bytes.as_slice()).read_to_end(&mut out)?;
+        Ok(out)
+      }
+      ObjectCompression::Zstd { .. } => {
+        Ok(zstd::stream::decode_all(bytes.as_slice())?)
+      }
+    }
+  }
+}
+
+/// Build the object key for `name` under `prefix`, keeping `Stamp::file_name()`
+/// as the trailing component so `load`/`refresh`/`clean` are prefix-agnostic.
+fn object_key(prefix: &str, name: &str) -> String {
+  if prefix.is_empty() {
+    name.to_string()
+  } else {
+    format!("{}/{}", prefix.trim_end_matches('/'), name)
+  }
+}
+
+/// List path passed to the object store so only keys under `prefix` are
+/// enumerated; bucket root when `prefix` is empty.
+fn object_list_path(prefix: &str) -> String {
+  if prefix.is_empty() {
+    String::from("/")
+  } else {
+    format!("{}/", prefix.trim_end_matches('/'))
+  }
+}
+
+/// Map a listing entry to the single logical blob name it should contribute, or
+/// `None` when the entry is internal bookkeeping. `.sha` sidecars and split
+/// `.partN` shards are dropped, a `.manifest` collapses to its base name so a
+/// split object surfaces exactly once, and `.zst` compressed variants drop
+/// their suffix.
+fn logical_listing_name(name: &str) -> Option<String> {
+  if name.ends_with(".sha") {
+    return None;
+  }
+  if let Some(idx) = name.rfind(".part") {
+    let suffix = &name[idx + 5..];
+    if !suffix.is_empty() && suffix.bytes().all(|b| b.is_ascii_digit()) {
+      return None;
+    }
+  }
+  if let Some(base) = name.strip_suffix(".manifest") {
+    return Some(base.to_string());
+  }
+  if let Some(base) = name.strip_suffix(".zst") {
+    return Some(base.to_string());
+  }
+  Some(name.to_string())
+}
+
+/// Recover the logical blob name from a full object key by dropping the prefix.
+fn object_name<'a>(prefix: &str, key: &'a str) -> &'a str {
+  if prefix.is_empty() {
+    key
+  } else {
+    key.strip_prefix(&format!("{}/", prefix.trim_end_matches('/'))).unwrap_or(key)
+  }
+}
+
+/// Transparent zstd compression for blobs stored through [`Storage::Local`].
+///
+/// Modelled on Garage's `DataBlock` Plain/Compressed split: a blob larger than
+/// `min_size` is written as a `.zst` sibling, everything else stays plain, and
+/// the read path resolves whichever variant is present (preferring the
+/// compressed one) so existing uncompressed archives keep working.
+#[derive(Clone, Debug, PartialEq, Serialize, Deserialize)]
+#[serde(rename_all = "camelCase")]
+pub struct Zstd {
+  /// zstd compression level (1-22).
+  pub level: i32,
+  /// Only compress blobs at least this many bytes; smaller ones stay plain.
+  pub min_size: u64,
+}
+
+/// Resolved on-disk variant of a stored blob, mirroring `DataBlockPath`.
+enum LocalBlock {
+  Plain(PathBuf),
+  Compressed(PathBuf),
+}
+
+impl LocalBlock {
+  /// Resolve `name` under `dir`, preferring the compressed `.zst` variant and
+  /// falling back to the plain one; `None` when neither exists.
+  fn resolve(dir: &str, name: &str) -> Option<Self> {
+    let compressed = Path::new(dir).join(format!("{}.zst", name));
+    if compressed.exists() {
+      return Some(LocalBlock::Compressed(compressed));
+    }
+    let plain = Path::new(dir).join(name);
+    if plain.exists() {
+      return Some(LocalBlock::Plain(plain));
+    }
+    None
   }
 }
 
 impl Display for Storage {
   fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
     match self {
-      Storage::Local { dir } => {
+      Storage::Local { dir, .. } => {
         write!(f, "Local ({})", dir)
       }
       Storage::ObjectStorage { bucket, .. } => {
@@ -77,11 +347,39 @@ impl Storage {
 
   pub(crate) async fn save<P: AsRef<Path>>(&self, from: P, name: String) -> anyhow::Result<()> {
 
+    // Oversized payloads are chunked into numbered parts plus a manifest; the
+    // manifest carries the overall digest so the read path can verify the
+    // reassembled object.
+    if let Some(Split { part_size }) = self.split() {
+      if fs::metadata(&from)?.len() > *part_size {
+        let mut bytes = Vec::new();
+        File::open(&from)?.read_to_end(&mut bytes)?;
+        let part_count = self.save_split(&bytes, &name, *part_size).await?;
+        debug!("File `{}` saved as {} part(s) on storage {}", &name, part_count, self);
+        return Ok(());
+      }
+    }
+
     match self {
-      Storage::Local {dir} => {
-        fs::copy(from, Path::new(&dir).join(&name))?;
+      Storage::Local { dir, compression, .. } => {
+        let plain = Path::new(&dir).join(&name);
+        let compressed = Path::new(&dir).join(format!("{}.zst", &name));
+
+        match compression {
+          Some(Zstd { level, min_size }) if fs::metadata(&from)?.len() >= *min_size => {
+            let mut input = BufReader::new(File::open(&from)?);
+            let output = File::create(&compressed)?;
+            zstd::stream::copy_encode(&mut input, output, *level)?;
+            // Drop any stale plain variant so the two can't diverge.
+            let _ = fs::remove_file(&plain);
+          },
+          _ => {
+            fs::copy(&from, &plain)?;
+            let _ = fs::remove_file(&compressed);
+          }
+        }
       },
-      Storage::ObjectStorage { endpoint, region, bucket , access_key, secret_key} => {
+      Storage::ObjectStorage { endpoint, region, bucket, access_key, secret_key, prefix, compression, .. } => {
         let mut storage = Bucket::new(&bucket, s3::Region::Custom{ region: region.clone(), endpoint: endpoint.clone() }, s3::creds::Credentials {
           access_key: Some(access_key.clone()),
           secret_key: Some(secret_key.clone()),
@@ -90,16 +388,14 @@ impl Storage {
         }).unwrap();
 
         storage.set_path_style();
-        storage.add_header("content-encoding", "gzip");
+        if let Some(encoding) = compression.content_encoding() {
+          storage.add_header("content-encoding", encoding);
+        }
         storage.add_header("cache-control", "public, max-age=604800, immutable");
 
-        let file = File::open(from)?;
-
-        let mut gz = flate2::bufread::GzEncoder::new(BufReader::new(file), Compression::best());
-        let mut buffer = Vec::new();
-        gz.read_to_end(&mut buffer)?;
+        let buffer = compression.encode(from)?;
 
-        let (_, status_code) = storage.put_object_with_content_type(&name, buffer.as_slice(), "application/json").await?;
+        let (_, status_code) = storage.put_object_with_content_type(&object_key(prefix, &name), buffer.as_slice(), "application/json").await?;
 
         if status_code != 200 {
           return Err(anyhow!("Error saving file to s3 bucket : {}", status_code));
@@ -112,13 +408,278 @@ impl Storage {
     Ok(())
   }
 
+  /// Raw object bytes from an object-storage backend; errors for `Local`.
+  async fn get_bytes(&self, name: String) -> anyhow::Result<Vec<u8>> {
+    match self {
+      Storage::ObjectStorage { endpoint, region, bucket, access_key, secret_key, prefix, .. } => {
+        let storage = Bucket::new(&bucket, s3::Region::Custom { region: region.clone(), endpoint: endpoint.clone() }, s3::creds::Credentials {
+          access_key: Some(access_key.clone()),
+          secret_key: Some(secret_key.clone()),
+          security_token: None,
+          session_token: None
+        }).unwrap();
+
+        let (buf, status_code) = storage.get_object(object_key(prefix, &name)).await?;
+        if status_code != 200 {
+          return Err(anyhow!("Error getting object from s3 bucket : {}", status_code));
+        }
+        Ok(buf)
+      }
+      Storage::Local { .. } => Err(anyhow!("get_bytes is only supported for object storage")),
+    }
+  }
+
+  /// Upload raw bytes under `name`; errors for `Local`.
+  async fn put_bytes(&self, name: String, bytes: &[u8]) -> anyhow::Result<()> {
+    match self {
+      Storage::ObjectStorage { endpoint, region, bucket, access_key, secret_key, prefix, .. } => {
+        let mut storage = Bucket::new(&bucket, s3::Region::Custom { region: region.clone(), endpoint: endpoint.clone() }, s3::creds::Credentials {
+          access_key: Some(access_key.clone()),
+          secret_key: Some(secret_key.clone()),
+          security_token: None,
+          session_token: None
+        }).unwrap();
+        storage.set_path_style();
+
+        let (_, status_code) = storage.put_object(&object_key(prefix, &name), bytes).await?;
+        if status_code != 200 {
+          return Err(anyhow!("Error saving object to s3 bucket : {}", status_code));
+        }
+        Ok(())
+      }
+      Storage::Local { .. } => Err(anyhow!("put_bytes is only supported for object storage")),
+    }
+  }
+
+  /// Multi-part configuration for this backend, if any.
+  fn split(&self) -> Option<&Split> {
+    match self {
+      Storage::Local { split, .. } => split.as_ref(),
+      Storage::ObjectStorage { split, .. } => split.as_ref(),
+    }
+  }
+
+  /// Write a raw blob under the literal key `key`, bypassing compression and the
+  /// content-hash sidecar; used for split parts and their manifest.
+  async fn raw_put(&self, key: &str, bytes: &[u8]) -> anyhow::Result<()> {
+    match self {
+      Storage::Local { dir, .. } => {
+        fs::write(Path::new(dir).join(key), bytes)?;
+        Ok(())
+      }
+      Storage::ObjectStorage { .. } => self.put_bytes(key.to_string(), bytes).await,
+    }
+  }
+
+  /// Read the raw bytes stored under the literal key `key`.
+  async fn raw_get(&self, key: &str) -> anyhow::Result<Vec<u8>> {
+    match self {
+      Storage::Local { dir, .. } => Ok(fs::read(Path::new(dir).join(key))?),
+      Storage::ObjectStorage { .. } => self.get_bytes(key.to_string()).await,
+    }
+  }
+
+  /// Delete the raw object stored under the literal key `key` (object storage).
+  async fn raw_delete(&self, key: &str) -> anyhow::Result<()> {
+    match self {
+      Storage::Local { dir, .. } => {
+        let _ = fs::remove_file(Path::new(dir).join(key));
+        Ok(())
+      }
+      Storage::ObjectStorage { endpoint, region, bucket, access_key, secret_key, prefix, .. } => {
+        let storage = Bucket::new(&bucket, s3::Region::Custom{ region: region.clone(), endpoint: endpoint.clone() }, s3::creds::Credentials {
+          access_key: Some(access_key.clone()),
+          secret_key: Some(secret_key.clone()),
+          security_token: None,
+          session_token: None
+        }).unwrap();
+        storage.delete_object(object_key(prefix, key)).await?;
+        Ok(())
+      }
+    }
+  }
+
+  /// Whether a split object's manifest is present for `name`.
+  async fn has_manifest(&self, name: &str) -> bool {
+    let key = format!("{}.manifest", name);
+    match self {
+      Storage::Local { dir, .. } => Path::new(dir).join(key).exists(),
+      Storage::ObjectStorage { .. } => self.get_bytes(key).await.is_ok(),
+    }
+  }
+
+  /// Chunk `bytes` into `part_size` parts written as `name.part{i}` siblings and
+  /// record them in `name.manifest`. Parts upload concurrently so the
+  /// object-store backend can transfer them in parallel. Returns the part count.
+  async fn save_split(&self, bytes: &[u8], name: &str, part_size: u64) -> anyhow::Result<usize> {
+    let jobs: Vec<(String, &[u8])> = bytes
+      .chunks(part_size as usize)
+      .enumerate()
+      .map(|(i, chunk)| (format!("{}.part{}", name, i), chunk))
+      .collect();
+
+    futures::future::try_join_all(jobs.iter().map(|(key, chunk)| self.raw_put(key, chunk))).await?;
+
+    let manifest = Manifest {
+      parts: jobs.iter().map(|(key, chunk)| ManifestPart { name: key.clone(), size: chunk.len() as u64 }).collect(),
+      digest: Self::hash_bytes(bytes),
+    };
+    self.raw_put(&format!("{}.manifest", name), &serde_json::to_vec(&manifest)?).await?;
+
+    Ok(jobs.len())
+  }
+
+  /// Reassemble a split object from its manifest, verifying each part's size and
+  /// the overall digest. A missing or mis-sized part is an error rather than a
+  /// truncated payload.
+  async fn get_split(&self, name: &str) -> anyhow::Result<Vec<u8>> {
+    let manifest: Manifest = serde_json::from_slice(&self.raw_get(&format!("{}.manifest", name)).await?)?;
+
+    let datas = futures::future::try_join_all(manifest.parts.iter().map(|p| self.raw_get(&p.name))).await?;
+
+    let mut out = Vec::with_capacity(manifest.parts.iter().map(|p| p.size as usize).sum());
+    for (part, data) in manifest.parts.iter().zip(datas.iter()) {
+      if data.len() as u64 != part.size {
+        return Err(anyhow!("Part `{}` size mismatch on storage {} : expected {}, got {}", part.name, self, part.size, data.len()));
+      }
+      out.extend_from_slice(data);
+    }
+
+    let digest = Self::hash_bytes(&out);
+    if digest != manifest.digest {
+      return Err(anyhow!("Integrity check failed for split object `{}` on storage {} : expected {}, got {}", name, self, manifest.digest, digest));
+    }
+
+    Ok(out)
+  }
+
+  /// Remove a split object's parts and manifest; no-op when none are present.
+  async fn remove_split(&self, name: &str) -> anyhow::Result<()> {
+    let manifest: Manifest = serde_json::from_slice(&self.raw_get(&format!("{}.manifest", name)).await?)?;
+    match self {
+      Storage::Local { dir, .. } => {
+        for part in &manifest.parts {
+          let _ = fs::remove_file(Path::new(dir).join(&part.name));
+        }
+        let _ = fs::remove_file(Path::new(dir).join(format!("{}.manifest", name)));
+      }
+      Storage::ObjectStorage { .. } => {
+        for part in &manifest.parts {
+          let _ = self.raw_delete(&part.name).await;
+        }
+        let _ = self.raw_delete(&format!("{}.manifest", name)).await;
+      }
+    }
+    Ok(())
+  }
+
+  /// SHA-256 of a local file, used for content-hash deduplication.
+  fn hash_file<P: AsRef<Path>>(from: P) -> anyhow::Result<String> {
+    use sha2::{Digest, Sha256};
+
+    let mut file = File::open(from)?;
+    let mut hasher = Sha256::new();
+    std::io::copy(&mut file, &mut hasher)?;
+    Ok(format!("{:x}", hasher.finalize()))
+  }
+
+  /// SHA-256 of an in-memory payload, matching [`hash_file`](Self::hash_file).
+  fn hash_bytes(bytes: &[u8]) -> String {
+    use sha2::{Digest, Sha256};
+
+    let mut hasher = Sha256::new();
+    hasher.update(bytes);
+    format!("{:x}", hasher.finalize())
+  }
+
+  /// Check `bytes` against the digest stored alongside `name`, if any. A
+  /// mismatch means the object was corrupted in transit or at rest and is
+  /// surfaced as an error instead of being silently deserialized; a missing
+  /// sidecar (archives written before checksums) passes through untouched.
+  async fn verify_integrity(&self, name: &str, bytes: &[u8]) -> anyhow::Result<()> {
+    if let Some(expected) = self.stored_hash(name).await? {
+      let actual = Self::hash_bytes(bytes);
+      if actual != expected {
+        return Err(anyhow!("Integrity check failed for `{}` on storage {} : expected {}, got {}", name, self, expected, actual));
+      }
+    }
+    Ok(())
+  }
+
+  /// Content hash recorded alongside a previously stored blob, if any.
+  pub(crate) async fn stored_hash(&self, name: &str) -> anyhow::Result<Option<String>> {
+    match self {
+      Storage::Local { dir, .. } => {
+        let sidecar = Path::new(dir).join(format!("{}.sha", name));
+        match fs::read_to_string(&sidecar) {
+          Ok(hash) => Ok(Some(hash.trim().to_string())),
+          Err(_) => Ok(None),
+        }
+      }
+      Storage::ObjectStorage { .. } => {
+        // Object stores keep the digest as a sibling `.sha` object.
+        match self.get_bytes(format!("{}.sha", name)).await {
+          Ok(bytes) => Ok(Some(String::from_utf8_lossy(&bytes).trim().to_string())),
+          Err(_) => Ok(None),
+        }
+      }
+    }
+  }
+
+  /// Save `from` as `name`, but skip the write (and its fsync/rename cost) when
+  /// the content hash matches what is already stored. Returns whether the blob
+  /// was actually written and its content hash, so callers can record it.
+  pub(crate) async fn save_if_changed<P: AsRef<Path>>(&self, from: P, name: String) -> anyhow::Result<(bool, String)> {
+    let hash = Self::hash_file(&from)?;
+
+    if let Some(existing) = self.stored_hash(&name).await? {
+      if existing == hash {
+        debug!("File `{}` unchanged ({}...), skipping write", &name, &hash[..12.min(hash.len())]);
+        return Ok((false, hash));
+      }
+    }
+
+    self.save(&from, name.clone()).await?;
+    self.store_hash(&name, &hash).await?;
+
+    Ok((true, hash))
+  }
+
+  async fn store_hash(&self, name: &str, hash: &str) -> anyhow::Result<()> {
+    match self {
+      Storage::Local { dir, .. } => {
+        fs::write(Path::new(dir).join(format!("{}.sha", name)), hash)?;
+        Ok(())
+      }
+      Storage::ObjectStorage { .. } => {
+        self.put_bytes(format!("{}.sha", name), hash.as_bytes()).await
+      }
+    }
+  }
+
   pub(crate) async fn remove(&self, name: String) -> anyhow::Result<()> {
 
+    // A split object lives as parts + manifest rather than a single blob.
+    if self.split().is_some() && self.has_manifest(&name).await {
+      self.remove_split(&name).await?;
+      let _ = self.raw_delete(&format!("{}.sha", name)).await;
+      return Ok(());
+    }
+
     match self {
-      Storage::Local { dir } => {
-        fs::remove_file(Path::new(dir).join(name))?;
+      Storage::Local { dir, .. } => {
+        match LocalBlock::resolve(dir, &name) {
+          Some(LocalBlock::Plain(path)) | Some(LocalBlock::Compressed(path)) => {
+            fs::remove_file(path)?;
+          },
+          None => {
+            return Err(anyhow!("File `{}` not found on storage {}", name, self));
+          }
+        }
+        // Drop the content-hash sidecar alongside the blob.
+        let _ = fs::remove_file(Path::new(dir).join(format!("{}.sha", &name)));
       }
-      Storage::ObjectStorage { endpoint, region, bucket, access_key, secret_key } => {
+      Storage::ObjectStorage { endpoint, region, bucket, access_key, secret_key, prefix, .. } => {
         let storage = Bucket::new(&bucket, s3::Region::Custom{ region: region.clone(), endpoint: endpoint.clone() }, s3::creds::Credentials {
           access_key: Some(access_key.clone()),
           secret_key: Some(secret_key.clone()),
@@ -126,7 +687,7 @@ impl Storage {
           session_token: None
         }).unwrap();
 
-        let (_, status_code) = storage.delete_object(name).await?;
+        let (_, status_code) = storage.delete_object(object_key(prefix, &name)).await?;
 
         if status_code != 204 {
           return Err(anyhow!("Error deleting file from s3 bucket : {}", status_code));
@@ -139,10 +700,11 @@ impl Storage {
 
   pub(crate) async fn exists(&self, name: String) -> anyhow::Result<bool> {
     match self {
-      Storage::Local { dir } => {
-        Ok(Path::new(dir).join(name).exists())
+      Storage::Local { dir, .. } => {
+        Ok(LocalBlock::resolve(dir, &name).is_some()
+          || Path::new(dir).join(format!("{}.manifest", name)).exists())
       }
-      Storage::ObjectStorage { endpoint, region, bucket, access_key, secret_key } => {
+      Storage::ObjectStorage { endpoint, region, bucket, access_key, secret_key, prefix, .. } => {
         let storage = Bucket::new(&bucket, s3::Region::Custom{ region: region.clone(), endpoint: endpoint.clone() }, s3::creds::Credentials {
           access_key: Some(access_key.clone()),
           secret_key: Some(secret_key.clone()),
@@ -150,18 +712,19 @@ impl Storage {
           session_token: None
         }).unwrap();
 
-        let list_result = storage.list(String::from("/"), Some(String::from("/"))).await?;
-        Ok(list_result.iter().find(|o| o.name == name).is_some())
+        let list_result = storage.list(object_list_path(prefix), Some(String::from("/"))).await?;
+        Ok(list_result.iter().any(|o| logical_listing_name(object_name(prefix, &o.name)).as_deref() == Some(name.as_str())))
       }
     }
   }
 
   pub(crate) fn exists_blocking(&self, name: String) -> anyhow::Result<bool> {
     match self {
-      Storage::Local { dir } => {
-        Ok(Path::new(dir).join(name).exists())
+      Storage::Local { dir, .. } => {
+        Ok(LocalBlock::resolve(dir, &name).is_some()
+          || Path::new(dir).join(format!("{}.manifest", name)).exists())
       }
-      Storage::ObjectStorage { endpoint, region, bucket, access_key, secret_key } => {
+      Storage::ObjectStorage { endpoint, region, bucket, access_key, secret_key, prefix, .. } => {
         let storage = Bucket::new(&bucket, s3::Region::Custom{ region: region.clone(), endpoint: endpoint.clone() }, s3::creds::Credentials {
           access_key: Some(access_key.clone()),
           secret_key: Some(secret_key.clone()),
@@ -169,16 +732,53 @@ impl Storage {
           session_token: None
         }).unwrap();
 
-        let list_result = storage.list_blocking(String::from("/"), Some(String::from("/")))?;
-        Ok(list_result.iter().find(|o| o.name == name).is_some())
+        let list_result = storage.list_blocking(object_list_path(prefix), Some(String::from("/")))?;
+        Ok(list_result.iter().any(|o| logical_listing_name(object_name(prefix, &o.name)).as_deref() == Some(name.as_str())))
+      }
+    }
+  }
+
+  /// Logical blob names currently present, as a set, in a single backend round
+  /// trip. Callers that need many membership checks (e.g. `refresh`) use this to
+  /// avoid a per-key query — crucial for object storage, where a blocking
+  /// per-key lookup inside a held lock would stall on remote latency.
+  pub(crate) async fn list_keys(&self) -> anyhow::Result<std::collections::HashSet<String>> {
+    match self {
+      Storage::Local { dir, .. } => {
+        let mut keys = std::collections::HashSet::new();
+        for entry in fs::read_dir(dir)? {
+          let entry = match entry { Ok(e) => e, Err(_) => continue };
+          if !entry.metadata().map(|m| m.is_file()).unwrap_or(false) {
+            continue;
+          }
+          let path = entry.path();
+          if let Some(name) = path.file_name().and_then(|n| n.to_str()) {
+            if let Some(logical) = logical_listing_name(name) {
+              keys.insert(logical);
+            }
+          }
+        }
+        Ok(keys)
+      }
+      Storage::ObjectStorage { endpoint, region, bucket, access_key, secret_key, prefix, .. } => {
+        let storage = Bucket::new(&bucket, s3::Region::Custom { region: region.clone(), endpoint: endpoint.clone() }, s3::creds::Credentials {
+          access_key: Some(access_key.clone()),
+          secret_key: Some(secret_key.clone()),
+          security_token: None,
+          session_token: None
+        }).unwrap();
+
+        let list_result = storage.list(object_list_path(prefix), Some(String::from("/"))).await?;
+        Ok(list_result.iter().filter_map(|o| logical_listing_name(object_name(prefix, &o.name))).collect())
       }
     }
   }
 
   pub(crate) async fn list(&self) -> anyhow::Result<Vec<Stamp>> {
     match self {
-      Storage::Local { dir } => {
+      Storage::Local { dir, .. } => {
         let mut stamps: Vec<Stamp> = Vec::new();
+        let mut seen = std::collections::HashSet::new();
 
         // Walk throw json files
         let paths = fs::read_dir(dir)?;
@@ -186,7 +786,20 @@ impl Storage {
           if let Ok(entry) = entry {
             if let Ok(metadata) = entry.metadata() {
               if metadata.is_file() {
-                stamps.push((&entry.path()).try_into()?);
+                // Resolve the single logical blob name for this entry, skipping
+                // `.sha` sidecars and split `.partN` shards and collapsing a
+                // split `.manifest` (and a `.zst` variant) back to one stamp.
+                let name = match entry.file_name().into_string() {
+                  Ok(name) => name,
+                  Err(_) => continue,
+                };
+                let logical = match logical_listing_name(&name) {
+                  Some(logical) => logical,
+                  None => continue,
+                };
+                if seen.insert(logical.clone()) {
+                  stamps.push(Stamp::try_from(&logical)?);
+                }
               }
             }
           }
@@ -194,7 +807,7 @@ impl Storage {
 
         Ok(stamps)
       }
-      Storage::ObjectStorage { endpoint, region, bucket, access_key, secret_key } => {
+      Storage::ObjectStorage { endpoint, region, bucket, access_key, secret_key, prefix, .. } => {
         let storage = Bucket::new(&bucket, s3::Region::Custom{ region: region.clone(), endpoint: endpoint.clone() }, s3::creds::Credentials {
           access_key: Some(access_key.clone()),
           secret_key: Some(secret_key.clone()),
@@ -202,28 +815,46 @@ impl Storage {
           session_token: None
         }).unwrap();
 
-        let list_result = storage.list(String::from("/"), Some(String::from("/"))).await?;
+        let list_result = storage.list(object_list_path(prefix), Some(String::from("/"))).await?;
+        let mut seen = std::collections::HashSet::new();
         Ok(list_result.iter().filter_map(|o| {
-          match Stamp::try_from(&o.name) {
-            Ok(stamp) => Some(stamp),
-            Err(_) => None,
+          let logical = logical_listing_name(object_name(prefix, &o.name))?;
+          if !seen.insert(logical.clone()) {
+            return None;
           }
+          Stamp::try_from(&logical).ok()
         }).collect())
       }
     }
   }
 
   pub(crate) async fn get(&self, name: String) -> anyhow::Result<Vec<Message>> {
+    if self.split().is_some() && self.has_manifest(&name).await {
+      let bytes = self.get_split(&name).await?;
+      return Ok(serde_json::from_slice(bytes.as_slice())?);
+    }
     match self {
-      Storage::Local { dir } => {
-        let f = File::open(Path::new(dir).join(name))?;
-        let f = BufReader::new(f);
+      Storage::Local { dir, .. } => {
+        // Materialise the decompressed payload so it can be checksum-verified
+        // against the sidecar digest before it is deserialized.
+        let mut bytes = Vec::new();
+        match LocalBlock::resolve(dir, &name) {
+          Some(LocalBlock::Compressed(path)) => {
+            zstd::stream::read::Decoder::new(File::open(path)?)?.read_to_end(&mut bytes)?;
+          },
+          Some(LocalBlock::Plain(path)) => {
+            BufReader::new(File::open(path)?).read_to_end(&mut bytes)?;
+          },
+          None => {
+            return Err(anyhow!("File `{}` not found on storage {}", name, self));
+          }
+        };
 
-        let messages: Vec<Message> = serde_json::from_reader(f)?;
+        self.verify_integrity(&name, &bytes).await?;
 
-        Ok(messages)
+        Ok(serde_json::from_slice(bytes.as_slice())?)
       }
-      Storage::ObjectStorage { endpoint, region, bucket, access_key, secret_key } => {
+      Storage::ObjectStorage { endpoint, region, bucket, access_key, secret_key, prefix, compression, .. } => {
         let storage = Bucket::new(&bucket, s3::Region::Custom{ region: region.clone(), endpoint: endpoint.clone() }, s3::creds::Credentials {
           access_key: Some(access_key.clone()),
           secret_key: Some(secret_key.clone()),
@@ -232,27 +863,129 @@ impl Storage {
         }).unwrap();
 
 
-        let (buf, status_code) = storage.get_object(name).await?;
-
-        let messages: Vec<Message> = serde_json::from_slice(buf.as_slice())?;
+        let (buf, status_code) = storage.get_object(object_key(prefix, &name)).await?;
 
         if status_code != 204 {
           return Err(anyhow!("Error getting file from s3 bucket : {}", status_code));
         }
 
+        let buf = compression.decode(buf)?;
+        self.verify_integrity(&name, &buf).await?;
+        let messages: Vec<Message> = serde_json::from_slice(buf.as_slice())?;
+
         Ok(messages)
       }
     }
   }
 
   pub(crate) async fn open(&self, name: String) -> anyhow::Result<BufReader<File>> {
+    if self.split().is_some() && self.has_manifest(&name).await {
+      // Reassemble the verified payload into a temp file and hand back a
+      // seekable handle, mirroring the compressed-`Local` branch.
+      let bytes = self.get_split(&name).await?;
+      let tmp = NamedTempFile::new()?;
+      {
+        use std::io::Write;
+        tmp.reopen()?.write_all(&bytes)?;
+      }
+      return Ok(BufReader::new(tmp.reopen()?));
+    }
     match self {
-      Storage::Local { dir } => {
-        let f = File::open(Path::new(dir).join(name))?;
-        Ok(BufReader::new(f))
+      Storage::Local { dir, .. } => {
+        match LocalBlock::resolve(dir, &name) {
+          Some(LocalBlock::Plain(path)) => {
+            // The plain blob is the decompressed payload, so its digest matches
+            // the stored sidecar directly.
+            if let Some(expected) = self.stored_hash(&name).await? {
+              let actual = Self::hash_file(&path)?;
+              if actual != expected {
+                return Err(anyhow!("Integrity check failed for `{}` on storage {} : expected {}, got {}", name, self, expected, actual));
+              }
+            }
+            Ok(BufReader::new(File::open(path)?))
+          },
+          Some(LocalBlock::Compressed(path)) => {
+            // zstd's streaming decoder is not `Seek`, so materialise the decoded
+            // bytes into a temp file and hand back a seekable handle to it. The
+            // `NamedTempFile` is unlinked on drop while the returned handle stays
+            // valid.
+            let tmp = NamedTempFile::new()?;
+            {
+              let mut decoder = zstd::stream::read::Decoder::new(File::open(path)?)?;
+              let mut out = tmp.reopen()?;
+              std::io::copy(&mut decoder, &mut out)?;
+            }
+            // Verify the materialised bytes before returning the seekable handle.
+            if let Some(expected) = self.stored_hash(&name).await? {
+              let actual = Self::hash_file(tmp.path())?;
+              if actual != expected {
+                return Err(anyhow!("Integrity check failed for `{}` on storage {} : expected {}, got {}", name, self, expected, actual));
+              }
+            }
+            Ok(BufReader::new(tmp.reopen()?))
+          },
+          None => {
+            Err(anyhow!("File `{}` not found on storage {}", name, self))
+          }
+        }
       }
-      Storage::ObjectStorage { .. } => {
-        todo!()
+      Storage::ObjectStorage { endpoint, region, bucket, access_key, secret_key, prefix, compression, .. } => {
+        use std::io::Write;
+
+        let storage = Bucket::new(&bucket, s3::Region::Custom{ region: region.clone(), endpoint: endpoint.clone() }, s3::creds::Credentials {
+          access_key: Some(access_key.clone()),
+          secret_key: Some(secret_key.clone()),
+          security_token: None,
+          session_token: None
+        }).unwrap();
+
+        let key = object_key(prefix, &name);
+
+        // Discover the object size so the body can be pulled in bounded ranges
+        // rather than a single buffered `get_object`.
+        let (head, status_code) = storage.head_object(&key).await?;
+        if status_code != 200 {
+          return Err(anyhow!("Error heading file from s3 bucket : {}", status_code));
+        }
+        let len = head.content_length.unwrap_or(0).max(0) as u64;
+
+        // Stream the (possibly compressed) body into a temp file through ranged
+        // GETs so the whole payload never sits in memory at once.
+        const RANGE_CHUNK: u64 = 8 * 1024 * 1024;
+        let raw = NamedTempFile::new()?;
+        {
+          let mut out = raw.reopen()?;
+          let mut start = 0u64;
+          while start < len {
+            let end = (start + RANGE_CHUNK).min(len) - 1;
+            let (chunk, status_code) = storage.get_object_range(&key, start, Some(end)).await?;
+            if status_code != 200 && status_code != 206 {
+              return Err(anyhow!("Error getting file range from s3 bucket : {}", status_code));
+            }
+            out.write_all(&chunk)?;
+            start = end + 1;
+          }
+          out.flush()?;
+        }
+
+        // Decode with the configured codec into a second temp file, matching the
+        // save path, and hand back a seekable reader over the decoded bytes.
+        let decoded = NamedTempFile::new()?;
+        {
+          let mut reader = compression.decode_stream(BufReader::new(raw.reopen()?))?;
+          let mut out = decoded.reopen()?;
+          std::io::copy(&mut reader, &mut out)?;
+        }
+
+        // Verify the decoded payload against the stored digest before returning.
+        if let Some(expected) = self.stored_hash(&name).await? {
+          let actual = Self::hash_file(decoded.path())?;
+          if actual != expected {
+            return Err(anyhow!("Integrity check failed for `{}` on storage {} : expected {}, got {}", name, self, expected, actual));
+          }
+        }
+
+        Ok(BufReader::new(decoded.reopen()?))
       }
     }
   }
@@ -0,0 +1,133 @@
+use std::collections::HashMap;
+use std::path::Path;
+use std::sync::Arc;
+use std::time::{Duration, SystemTime};
+use tokio::sync::RwLock;
+use tokio::task::JoinHandle;
+use crate::config::{Config, ProviderConfig};
+use crate::providers::{self, Winds};
+
+/// Live handle onto the running provider fleet, kept in sync with the config
+/// file by a background poller. Newly-enabled providers are spawned through the
+/// existing [`providers::start_provider_task`], disabled ones have their
+/// background task aborted and their `Winds` dropped, and changed parameters
+/// restart the provider in place.
+pub struct ConfigWatcher {
+  providers: Arc<RwLock<HashMap<String, Winds>>>,
+  handles: Arc<RwLock<HashMap<String, JoinHandle<()>>>>,
+  configs: Arc<RwLock<HashMap<String, ProviderConfig>>>,
+  _watcher: JoinHandle<()>,
+}
+
+impl ConfigWatcher {
+  /// Shared map of live providers, so the API (or another subsystem) can read
+  /// the current fleet without going through the watcher.
+  pub fn providers(&self) -> Arc<RwLock<HashMap<String, Winds>>> {
+    self.providers.clone()
+  }
+
+  /// Point-in-time copy of the live providers.
+  pub async fn snapshot(&self) -> HashMap<String, Winds> {
+    self.providers.read().await.clone()
+  }
+}
+
+/// Load `path`, start every enabled provider, and spawn a poller that reapplies
+/// the file whenever its modification time changes.
+pub async fn spawn_config_watcher(path: String) -> ConfigWatcher {
+  let providers = Arc::new(RwLock::new(HashMap::new()));
+  let handles = Arc::new(RwLock::new(HashMap::new()));
+  let configs = Arc::new(RwLock::new(HashMap::new()));
+
+  reconcile(&path, &providers, &handles, &configs).await;
+
+  let watcher = {
+    let path = path.clone();
+    let providers = providers.clone();
+    let handles = handles.clone();
+    let configs = configs.clone();
+    tokio::spawn(async move {
+      let mut last = last_modified(&path);
+      loop {
+        tokio::time::sleep(Duration::from_secs(10)).await;
+        let current = last_modified(&path);
+        if current != last {
+          last = current;
+          info!("config-watcher - `{}` changed, reconciling providers", path);
+          reconcile(&path, &providers, &handles, &configs).await;
+        }
+      }
+    })
+  };
+
+  ConfigWatcher { providers, handles, configs, _watcher: watcher }
+}
+
+fn last_modified(path: &str) -> Option<SystemTime> {
+  std::fs::metadata(path).and_then(|m| m.modified()).ok()
+}
+
+/// Diff the desired config against the running fleet and apply the difference.
+async fn reconcile(
+  path: &str,
+  providers: &Arc<RwLock<HashMap<String, Winds>>>,
+  handles: &Arc<RwLock<HashMap<String, JoinHandle<()>>>>,
+  configs: &Arc<RwLock<HashMap<String, ProviderConfig>>>,
+) {
+  let config: Config = match confy::load_path(Path::new(path)) {
+    Ok(config) => config,
+    Err(e) => {
+      error!("config-watcher - failed to load `{}` : {:?}", path, e);
+      return;
+    }
+  };
+
+  let desired: HashMap<String, ProviderConfig> = config.providers
+      .into_iter()
+      .map(|c| (c.key().to_string(), c))
+      .collect();
+
+  // Stop anything that is gone, has been disabled, or whose parameters changed;
+  // a changed provider is torn down here and started fresh below.
+  let live: Vec<String> = configs.read().await.keys().cloned().collect();
+  for key in live {
+    let should_stop = match desired.get(&key) {
+      None => true,
+      Some(c) => !c.enabled() || configs.read().await.get(&key) != Some(c),
+    };
+    if should_stop {
+      stop(&key, providers, handles, configs).await;
+    }
+  }
+
+  // Start every enabled provider that isn't already running.
+  for (key, cfg) in &desired {
+    if !cfg.enabled() || configs.read().await.contains_key(key) {
+      continue;
+    }
+    match providers::start_provider_task(cfg).await {
+      Ok(Some((winds, handle))) => {
+        providers.write().await.insert(key.clone(), winds);
+        handles.write().await.insert(key.clone(), handle);
+        configs.write().await.insert(key.clone(), cfg.clone());
+        info!("config-watcher - provider `{}` started", key);
+      },
+      Ok(None) => {},
+      Err(e) => error!("config-watcher - failed to start provider `{}` : {:?}", key, e),
+    }
+  }
+}
+
+async fn stop(
+  key: &str,
+  providers: &Arc<RwLock<HashMap<String, Winds>>>,
+  handles: &Arc<RwLock<HashMap<String, JoinHandle<()>>>>,
+  configs: &Arc<RwLock<HashMap<String, ProviderConfig>>>,
+) {
+  if let Some(handle) = handles.write().await.remove(key) {
+    handle.abort();
+  }
+  providers.write().await.remove(key);
+  configs.write().await.remove(key);
+  info!("config-watcher - provider `{}` stopped", key);
+}
@@ -0,0 +1,143 @@
+use std::io::{Read, Seek, SeekFrom};
+use std::num::NonZeroUsize;
+use std::sync::Arc;
+use lru::LruCache;
+use crate::grib::{GribError, Result, SECT8_ES_SIZE};
+use crate::grib::reader::{take_seek, FromReader};
+use crate::grib::sections::Indicator;
+use crate::grib::sections::sect3::GridDefinition;
+use crate::grib::sections::sect4::ProductDefinition;
+use crate::grib::sections::sect5::DataRepresentationDefinition;
+use crate::read_as;
+
+const SECT_HEADER_SIZE: u64 = 5;
+
+/// Everything needed to decide whether a message is worth decoding, plus the
+/// byte range of its Section 7 payload. Sections 1 (identification), 2 (local
+/// use) and 6 (bitmap) are skipped without being read, since nothing downstream
+/// of [`GribIndex::scan`] needs them.
+pub(crate) struct MessageIndex {
+    pub(crate) discipline: u8,
+    pub(crate) grid_definition: GridDefinition,
+    pub(crate) product_definition: ProductDefinition,
+    data_representation_definition: DataRepresentationDefinition,
+    data_range: (u64, u64),
+}
+
+/// Offset index over the messages of a GRIB2 file: every message's Section
+/// 3/4/5 is parsed in full (cheap, and needed to tell which messages are worth
+/// decoding) while Section 7 is left unread and only its byte range is
+/// recorded, so a caller can seek straight to the payload it needs instead of
+/// buffering every message up front.
+pub(crate) struct GribIndex {
+    pub(crate) messages: Vec<MessageIndex>,
+}
+
+impl GribIndex {
+    pub(crate) fn scan<R: Read + Seek>(reader: &mut R) -> Result<Self> {
+        let mut messages = Vec::new();
+
+        loop {
+            let offset = reader.stream_position()?;
+            let indicator = match Indicator::from_reader(reader) {
+                Ok(indicator) => indicator,
+                Err(GribError::StdError(_)) | Err(GribError::NotGRIB()) => break,
+                Err(err) => return Err(err),
+            };
+            let message_end = offset + indicator.total_length;
+
+            let mut grid_definition = None;
+            let mut product_definition = None;
+            let mut data_representation_definition = None;
+            let mut data_range = (0u64, 0u64);
+
+            while reader.stream_position()? < message_end - SECT8_ES_SIZE as u64 {
+                let section_start = reader.stream_position()?;
+                let mut header = [0u8; SECT_HEADER_SIZE as usize];
+                reader.read_exact(&mut header)?;
+                let length = read_as!(u32, header, 0) as u64;
+                let number = header[4];
+                reader.seek(SeekFrom::Start(section_start))?;
+
+                match number {
+                    3 => {
+                        grid_definition = Some(GridDefinition::from_reader(reader)?);
+                    }
+                    4 => {
+                        product_definition = Some(ProductDefinition::from_reader(reader)?);
+                    }
+                    5 => {
+                        data_representation_definition = Some(DataRepresentationDefinition::from_reader(reader)?);
+                    }
+                    7 => {
+                        data_range = (section_start + SECT_HEADER_SIZE, section_start + length);
+                        reader.seek(SeekFrom::Start(section_start + length))?;
+                    }
+                    _ => {
+                        reader.seek(SeekFrom::Start(section_start + length))?;
+                    }
+                }
+            }
+
+            reader.seek(SeekFrom::Start(message_end))?;
+
+            messages.push(MessageIndex {
+                discipline: indicator.discipline,
+                grid_definition: grid_definition.ok_or_else(|| GribError::DecodeError(String::from("Missing Section 3")))?,
+                product_definition: product_definition.ok_or_else(|| GribError::DecodeError(String::from("Missing Section 4")))?,
+                data_representation_definition: data_representation_definition.ok_or_else(|| GribError::DecodeError(String::from("Missing Section 5")))?,
+                data_range,
+            });
+        }
+
+        Ok(Self { messages })
+    }
+}
+
+/// Random-access reader over a single seekable GRIB2 source backed by a
+/// [`GribIndex`]: [`decode`](Self::decode) reads and unpacks only the Section 7
+/// payload of the message asked for, and keeps the result in a bounded LRU so
+/// asking for the same message twice does not seek and decode it again.
+pub(crate) struct GribAccessor<R> {
+    reader: R,
+    index: GribIndex,
+    cache: LruCache<usize, Arc<Box<[f64]>>>,
+}
+
+impl<R: Read + Seek> GribAccessor<R> {
+    pub(crate) fn open(mut reader: R, capacity: usize) -> Result<Self> {
+        let index = GribIndex::scan(&mut reader)?;
+        Ok(Self {
+            reader,
+            index,
+            cache: LruCache::new(NonZeroUsize::new(capacity.max(1)).unwrap()),
+        })
+    }
+
+    pub(crate) fn messages(&self) -> &[MessageIndex] {
+        &self.index.messages
+    }
+
+    /// Decoded grid for the message at `position`, seeking straight to its
+    /// Section 7 byte range on a cache miss instead of re-parsing the file.
+    pub(crate) fn decode(&mut self, position: usize) -> Result<Arc<Box<[f64]>>> {
+        if let Some(grid) = self.cache.get(&position) {
+            return Ok(grid.clone());
+        }
+
+        let message = self.index.messages.get(position)
+            .ok_or_else(|| GribError::DecodeError(String::from("Message index out of range")))?;
+
+        let (start, end) = message.data_range;
+        self.reader.seek(SeekFrom::Start(start))?;
+        let mut bytes = vec![0u8; (end - start) as usize];
+        take_seek(&mut self.reader, end - start)?.read_exact(&mut bytes)?;
+
+        let grid = Arc::new(
+            message.data_representation_definition.data.decode(message.data_representation_definition.num_points, &bytes)?
+        );
+
+        self.cache.put(position, grid.clone());
+        Ok(grid)
+    }
+}
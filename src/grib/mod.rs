@@ -1,8 +1,10 @@
 pub mod sections;
+pub(crate) mod index;
+pub(crate) mod reader;
 mod utils;
 
 use std::collections::HashMap;
-use std::io::Read;
+use std::io::{BufRead, Read, Write};
 use chrono::{TimeZone, Utc};
 use crate::grib::sections::{SectionHeader, Indicator, Section};
 use crate::grib::sections::sect1::Identification;
@@ -10,17 +12,14 @@ use crate::grib::sections::sect3::{Grid, GridDefinition};
 use crate::grib::sections::sect4::{Product, ProductDefinition};
 use crate::grib::sections::sect5::{Data, DataRepresentationDefinition};
 use crate::grib::sections::sect6::BitMap;
-use crate::grib::sections::sect7::complex::GridPointDataComplexPackingDecoder;
-use crate::grib::sections::sect7::complex_spacial_diff::GridPointDataComplexPackingSpacialDiffDecoder;
-use crate::grib::sections::sect7::Grib2DataDecoder;
-use crate::grib::sections::sect7::simple::GridPointDataSimplePackingDecoder;
+use crate::grib::sections::sect7::simple::GridPointDataSimplePackingEncoder;
 
 const SECT0_IS_MAGIC: &[u8] = b"GRIB";
 const SECT0_IS_MAGIC_SIZE: usize = SECT0_IS_MAGIC.len();
 const SECT0_IS_SIZE: usize = 16;
 const SECT_HEADER_SIZE: usize = 5;
 const SECT8_ES_MAGIC: &[u8] = b"7777";
-const SECT8_ES_SIZE: usize = SECT8_ES_MAGIC.len();
+pub(crate) const SECT8_ES_SIZE: usize = SECT8_ES_MAGIC.len();
 
 #[macro_export]
 macro_rules! read_as {
@@ -56,27 +55,82 @@ pub(crate) struct Message {
 
 impl Message {
     pub(crate) fn decode(&self) -> Result<Box<[f64]>> {
+        self.data_representation_definition.data.decode(
+            self.data_representation_definition.num_points,
+            &self.data,
+        )
+    }
+
+    /// Pack a grid of `f64` values into a Section 7 payload, the counterpart to
+    /// [`Message::decode`]. Only simple packing (Data Representation Template
+    /// 5.0) is supported for now; other packings return a `DecodeError`.
+    pub(crate) fn encode(&self, values: &[f64]) -> Result<Box<[u8]>> {
         match &self.data_representation_definition.data {
             Data::Data0(data0) => {
-                Ok(GridPointDataSimplePackingDecoder{}.decode(&self.data_representation_definition, &self.data)?)
-            }
-            Data::Data2(data2) => {
-                Ok(GridPointDataComplexPackingDecoder{}.decode(&self.data_representation_definition, &self.data)?)
+                Ok(GridPointDataSimplePackingEncoder{}.encode(data0, values)?)
             }
-            Data::Data3(data3) => {
-                Ok(GridPointDataComplexPackingSpacialDiffDecoder{}.decode(&self.data_representation_definition, &self.data)?)
-            }
-            Data::Unknown(_) => {
-                error!("Not implemented data decoder {}", self.data_representation_definition.template_number);
-                Err(GribError::DecodeError(format!("Not implemented data decoder : {}", self.data_representation_definition.template_number)))
+            _ => {
+                Err(GribError::DecodeError(format!("Not implemented data encoder : {}", self.data_representation_definition.template_number)))
             }
         }
     }
+
+    /// Serialize this message as a conformant GRIB2 message, the inverse of the
+    /// section readers. Each section body is built first so the 5-byte
+    /// `<u32 length><u8 number>` headers and the Section 0 total length can be
+    /// back-patched from the final sizes.
+    pub(crate) fn to_writer<W: Write>(&self, writer: &mut W) -> Result<()> {
+        let mut bitmap = Vec::with_capacity(1 + self.bitmap.bitmap.len());
+        bitmap.push(self.bitmap.bitmap_indicator);
+        bitmap.extend_from_slice(&self.bitmap.bitmap);
+
+        let sections: [(u8, Vec<u8>); 6] = [
+            (1, self.identification.encode()),
+            (3, self.grid_definition.encode()),
+            (4, self.product_definition.encode()),
+            (5, self.data_representation_definition.encode()),
+            (6, bitmap),
+            (7, self.data.to_vec()),
+        ];
+
+        let mut body = Vec::new();
+        for (number, section) in &sections {
+            let length = (SECT_HEADER_SIZE + section.len()) as u32;
+            body.extend_from_slice(&length.to_be_bytes());
+            body.push(*number);
+            body.extend_from_slice(section);
+        }
+        body.extend_from_slice(SECT8_ES_MAGIC);
+
+        let total_length = (SECT0_IS_SIZE + body.len()) as u64;
+
+        let mut indicator = Vec::with_capacity(SECT0_IS_SIZE);
+        indicator.extend_from_slice(SECT0_IS_MAGIC);
+        indicator.extend_from_slice(&[0, 0]); // reserved
+        indicator.push(self.indicator.discipline);
+        indicator.push(2); // edition number
+        indicator.extend_from_slice(&total_length.to_be_bytes());
+
+        writer.write_all(&indicator)?;
+        writer.write_all(&body)?;
+
+        Ok(())
+    }
+}
+
+impl Grib {
+    /// Serialize every message back to the writer in order.
+    pub(crate) fn to_writer<W: Write>(&self, writer: &mut W) -> Result<()> {
+        for message in &self.messages {
+            message.to_writer(writer)?;
+        }
+        Ok(())
+    }
 }
 
-pub(crate) fn from_reader<R: Read>(reader: R) -> Result<Grib, GribError> {
+pub(crate) fn from_reader<R: Read + 'static>(reader: R) -> Result<Grib, GribError> {
 
-    let mut reader = GribReader::new(reader);
+    let mut reader = GribReader::new(reader)?;
 
     let mut messages = Vec::new();
 
@@ -143,11 +197,11 @@ pub(crate) fn from_reader<R: Read>(reader: R) -> Result<Grib, GribError> {
     })
 }
 
-struct GribReader<R: Read> {
-    reader: R,
+struct GribReader {
+    reader: Box<dyn Read>,
 }
 
-impl<R: Read> Read for GribReader<R> {
+impl Read for GribReader {
     fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
         self.reader.read(buf)
     }
@@ -157,10 +211,49 @@ impl<R: Read> Read for GribReader<R> {
     }
 }
 
+/// Whether the leading bytes of a GRIB source match a compression format
+/// [`sniff_compression`] would wrap. Exposed so seek-based readers (the
+/// [`index`] module) can tell up front whether they can work directly against
+/// the source or must fall back to this module's buffered, streaming decode.
+pub(crate) fn is_compressed(head: &[u8]) -> bool {
+    (head.len() >= 2 && head[..2] == [0x1f, 0x8b])
+        || (head.len() >= 4 && head[..4] == [0x28, 0xb5, 0x2f, 0xfd])
+        || (head.len() >= 3 && head[..3] == *b"BZh")
+        || (head.len() >= 6 && head[..6] == [0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00])
+}
+
+/// Peek the leading magic bytes and, when they match a known compression
+/// format, wrap the stream in the matching streaming decoder so every section
+/// read below sees decompressed bytes. The peeked bytes are buffered back
+/// through the `BufReader` rather than consumed, so `read_sect0` still sees the
+/// `GRIB` magic untouched. Uncompressed GRIB falls through unwrapped.
+fn sniff_compression<R: Read + 'static>(reader: R) -> Result<Box<dyn Read>> {
+    let mut reader = std::io::BufReader::new(reader);
+
+    let mut head = [0u8; 6];
+    let n = {
+        let peek = reader.fill_buf()?;
+        let n = peek.len().min(head.len());
+        head[..n].copy_from_slice(&peek[..n]);
+        n
+    };
+
+    if n >= 2 && head[..2] == [0x1f, 0x8b] {
+        Ok(Box::new(flate2::read::MultiGzDecoder::new(reader)))
+    } else if n >= 4 && head[..4] == [0x28, 0xb5, 0x2f, 0xfd] {
+        Ok(Box::new(zstd::stream::read::Decoder::new(reader)?))
+    } else if n >= 3 && &head[..3] == b"BZh" {
+        Ok(Box::new(bzip2::read::BzDecoder::new(reader)))
+    } else if n >= 6 && head == [0xfd, 0x37, 0x7a, 0x58, 0x5a, 0x00] {
+        Ok(Box::new(xz2::read::XzDecoder::new(reader)))
+    } else {
+        Ok(Box::new(reader))
+    }
+}
 
-impl<R: Read> GribReader<R> {
-    fn new(reader: R) -> Self {
-        Self { reader }
+impl GribReader {
+    fn new<R: Read + 'static>(reader: R) -> Result<Self> {
+        Ok(Self { reader: sniff_compression(reader)? })
     }
 
     fn read_sect0(&mut self) -> Result<(SectionHeader, Indicator)> {
@@ -0,0 +1,176 @@
+use std::io::{Read, Seek, SeekFrom};
+use crate::grib::{GribError, Result};
+use crate::grib::sections::Indicator;
+use crate::grib::sections::sect3::{Grid, GridDefinition};
+use crate::grib::sections::sect4::{Product, ProductDefinition};
+use crate::grib::sections::sect5::{Data, DataRepresentationDefinition};
+use crate::read_as;
+
+const SECT0_IS_SIZE: usize = 16;
+const SECT_HEADER_SIZE: usize = 5;
+
+/// A section struct that can read itself from any seekable reader, following
+/// decomp-toolkit's `FromReader`: parsing is framed by `read_exact` and each
+/// implementation leaves the reader positioned at the end of its own section
+/// (skipping any trailing bytes it did not need, such as an optional list of
+/// numbers) so callers can parse sections back to back without re-deriving
+/// their length.
+pub(crate) trait FromReader: Sized {
+    fn from_reader<R: Read + Seek>(reader: &mut R) -> Result<Self>;
+}
+
+/// A bounded sub-reader over `[start, start + len)` of an underlying seekable
+/// reader, mirroring decomp-toolkit's `take_seek`: reads are clamped to the
+/// range and seeks are relative to `start`, so a section's byte range can be
+/// handed out as an independent, self-contained reader without buffering the
+/// sections around it.
+pub(crate) struct TakeSeek<'a, R: Read + Seek> {
+    inner: &'a mut R,
+    start: u64,
+    len: u64,
+    pos: u64,
+}
+
+pub(crate) fn take_seek<R: Read + Seek>(inner: &mut R, len: u64) -> Result<TakeSeek<'_, R>> {
+    let start = inner.stream_position()?;
+    Ok(TakeSeek { inner, start, len, pos: 0 })
+}
+
+impl<'a, R: Read + Seek> Read for TakeSeek<'a, R> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        let remaining = self.len.saturating_sub(self.pos);
+        if remaining == 0 {
+            return Ok(0);
+        }
+        let to_read = remaining.min(buf.len() as u64) as usize;
+        let read = self.inner.read(&mut buf[..to_read])?;
+        self.pos += read as u64;
+        Ok(read)
+    }
+}
+
+impl<'a, R: Read + Seek> Seek for TakeSeek<'a, R> {
+    fn seek(&mut self, pos: SeekFrom) -> std::io::Result<u64> {
+        let target = match pos {
+            SeekFrom::Start(n) => n,
+            SeekFrom::End(n) => (self.len as i64 + n) as u64,
+            SeekFrom::Current(n) => (self.pos as i64 + n) as u64,
+        };
+        self.inner.seek(SeekFrom::Start(self.start + target))?;
+        self.pos = target;
+        Ok(self.pos)
+    }
+}
+
+fn read_header<R: Read + Seek>(reader: &mut R) -> Result<(usize, u8)> {
+    let mut buf = [0u8; SECT_HEADER_SIZE];
+    reader.read_exact(&mut buf)?;
+    Ok((read_as!(u32, buf, 0) as usize, buf[4]))
+}
+
+impl FromReader for Indicator {
+    fn from_reader<R: Read + Seek>(reader: &mut R) -> Result<Self> {
+        let mut buf = [0u8; SECT0_IS_SIZE];
+        reader.read_exact(&mut buf)?;
+
+        if &buf[0..4] != b"GRIB" {
+            return Err(GribError::NotGRIB());
+        }
+        let version = buf[7];
+        if version != 2 {
+            return Err(GribError::GRIBVersionMismatch(version));
+        }
+
+        Ok(Indicator {
+            discipline: buf[6],
+            total_length: read_as!(u64, buf, 8),
+        })
+    }
+}
+
+impl FromReader for GridDefinition {
+    fn from_reader<R: Read + Seek>(reader: &mut R) -> Result<Self> {
+        let start = reader.stream_position()?;
+        let (length, _number) = read_header(reader)?;
+
+        let mut buf = [0u8; 9];
+        reader.read_exact(&mut buf)?;
+
+        let template_number = read_as!(u16, buf, 7);
+        let optional_num_list_size = buf[5] as usize;
+
+        let template_size = length - SECT_HEADER_SIZE - buf.len() - optional_num_list_size;
+        let mut template = vec![0u8; template_size];
+        reader.read_exact(&mut template)?;
+
+        let grid = Grid::from_template(template_number, template)?;
+
+        let definition = GridDefinition {
+            source: buf[0],
+            num_points: read_as!(u32, buf, 1) as usize,
+            optional_num_list_size,
+            optional_num_list_interpretation: buf[6],
+            template_number,
+            grid,
+        };
+
+        reader.seek(SeekFrom::Start(start + length as u64))?;
+        Ok(definition)
+    }
+}
+
+impl FromReader for ProductDefinition {
+    fn from_reader<R: Read + Seek>(reader: &mut R) -> Result<Self> {
+        let start = reader.stream_position()?;
+        let (length, _number) = read_header(reader)?;
+
+        let mut buf = [0u8; 4];
+        reader.read_exact(&mut buf)?;
+
+        let template_number = read_as!(u16, buf, 2);
+        let num_coordinates = read_as!(u16, buf, 0);
+
+        let template_size = length - SECT_HEADER_SIZE - buf.len() - 4 * num_coordinates as usize;
+        let mut template = vec![0u8; template_size];
+        reader.read_exact(&mut template)?;
+
+        let product = Product::from_template(template_number, template)?;
+
+        let definition = ProductDefinition {
+            num_coordinates,
+            template_number,
+            product,
+            coordinates: None,
+        };
+
+        reader.seek(SeekFrom::Start(start + length as u64))?;
+        Ok(definition)
+    }
+}
+
+impl FromReader for DataRepresentationDefinition {
+    fn from_reader<R: Read + Seek>(reader: &mut R) -> Result<Self> {
+        let start = reader.stream_position()?;
+        let (length, _number) = read_header(reader)?;
+
+        let mut buf = [0u8; 6];
+        reader.read_exact(&mut buf)?;
+
+        let template_number = read_as!(u16, buf, 4);
+
+        let template_size = length - SECT_HEADER_SIZE - buf.len();
+        let mut template = vec![0u8; template_size];
+        reader.read_exact(&mut template)?;
+
+        let data = Data::from_template(template_number, template)?;
+
+        let definition = DataRepresentationDefinition {
+            num_points: read_as!(u32, buf, 0) as usize,
+            template_number,
+            data,
+        };
+
+        reader.seek(SeekFrom::Start(start + length as u64))?;
+        Ok(definition)
+    }
+}
@@ -1,4 +1,4 @@
-use chrono::{DateTime, Utc};
+use chrono::{DateTime, Datelike, Timelike, Utc};
 
 #[derive(Debug, Clone, PartialEq, Eq)]
 pub struct Identification {
@@ -20,3 +20,25 @@ pub struct Identification {
     /// Type of processed data in this GRIB message (see Code Table 1.4)
     pub data_type: u8,
 }
+
+impl Identification {
+    /// Encode the Section 1 body (octets 6 onwards), the inverse of
+    /// `read_sect1_body`.
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::with_capacity(16);
+        buf.extend_from_slice(&self.centre_id.to_be_bytes());
+        buf.extend_from_slice(&self.subcentre_id.to_be_bytes());
+        buf.push(self.master_table_version);
+        buf.push(self.local_table_version);
+        buf.push(self.ref_time_significance);
+        buf.extend_from_slice(&(self.ref_time.year() as u16).to_be_bytes());
+        buf.push(self.ref_time.month() as u8);
+        buf.push(self.ref_time.day() as u8);
+        buf.push(self.ref_time.hour() as u8);
+        buf.push(self.ref_time.minute() as u8);
+        buf.push(self.ref_time.second() as u8);
+        buf.push(self.prod_status);
+        buf.push(self.data_type);
+        buf
+    }
+}
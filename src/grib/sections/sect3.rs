@@ -48,6 +48,54 @@ impl Grid {
             }
         }
     }
+
+    /// Encode the grid template (octet 15 onwards of Section 3), the inverse of
+    /// `from_template`.
+    pub(crate) fn encode_template(&self) -> Vec<u8> {
+        match self {
+            Grid::Grid0(grid) => {
+                let mut buf = Vec::with_capacity(58);
+                buf.push(grid.header.earth_shape);
+                buf.push(grid.header.spherical_radius.scale);
+                buf.extend_from_slice(&grid.header.spherical_radius.value.to_be_bytes());
+                buf.push(grid.header.major_axis.scale);
+                buf.extend_from_slice(&grid.header.major_axis.value.to_be_bytes());
+                buf.push(grid.header.minor_axis.scale);
+                buf.extend_from_slice(&grid.header.minor_axis.value.to_be_bytes());
+                buf.extend_from_slice(&grid.n_i.to_be_bytes());
+                buf.extend_from_slice(&grid.n_j.to_be_bytes());
+                buf.extend_from_slice(&grid.initial_prod_basic_angle.basic_angle.to_be_bytes());
+                buf.extend_from_slice(&grid.initial_prod_basic_angle.basic_angle_sub.to_be_bytes());
+                buf.extend_from_slice(&grid.la1.to_be_bytes());
+                buf.extend_from_slice(&grid.lo1.to_be_bytes());
+                buf.push(grid.resolution_and_component_flags);
+                buf.extend_from_slice(&grid.la2.to_be_bytes());
+                buf.extend_from_slice(&grid.lo2.to_be_bytes());
+                buf.extend_from_slice(&grid.d_i.to_be_bytes());
+                buf.extend_from_slice(&grid.d_j.to_be_bytes());
+                buf.push(grid.scanning_mode);
+                buf
+            }
+            Grid::Unknown(bytes) => bytes.clone(),
+        }
+    }
+}
+
+impl GridDefinition {
+    /// Encode the Section 3 body (octets 6 onwards), the inverse of
+    /// `read_sect3_body`. The optional list of numbers is not retained on parse,
+    /// so it is re-emitted as zero octets to preserve the declared size.
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.push(self.source);
+        buf.extend_from_slice(&(self.num_points as u32).to_be_bytes());
+        buf.push(self.optional_num_list_size as u8);
+        buf.push(self.optional_num_list_interpretation);
+        buf.extend_from_slice(&self.template_number.to_be_bytes());
+        buf.extend_from_slice(&self.grid.encode_template());
+        buf.extend(std::iter::repeat(0).take(self.optional_num_list_size));
+        buf
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -15,6 +15,9 @@ pub struct ProductDefinition {
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub enum Product {
     Product0(Product0),
+    Product1(Product1),
+    Product8(Product8),
+    Product11(Product11),
     Unknown(Vec<u8>)
 }
 
@@ -23,41 +26,47 @@ impl Product {
 
         match template_number {
             0 => {
-                Ok(Product::Product0(Product0 {
-                    parameter_category: buf[0],
-                    parameter_number: buf[1],
-                    process_type: buf[2],
-                    background_process: buf[3],
-                    analysis_process: buf[4],
-                    hours: read_as!(u16, buf, 5),
-                    minutes: buf[7],
-                    forecast_time: match buf[8] {
-                        0 => Duration::minutes(read_as!(u32, buf, 9) as i64),
-                        1 => Duration::hours(read_as!(u32, buf, 9) as i64),
-                        2 => Duration::days(read_as!(u32, buf, 9) as i64),
-                        3 => Duration::days(30 * read_as!(u32, buf, 9) as i64),
-                        4 => Duration::days(365 * read_as!(u32, buf, 9) as i64),
-                        5 => Duration::days(10 * 365 * read_as!(u32, buf, 9) as i64),
-                        6 => Duration::days(30 * 365 * read_as!(u32, buf, 9) as i64),
-                        7 => Duration::days(100 * 365 * read_as!(u32, buf, 9) as i64),
-                        10 => Duration::hours(3 * read_as!(u32, buf, 9) as i64),
-                        11 => Duration::hours(6 * read_as!(u32, buf, 9) as i64),
-                        12 => Duration::hours(12 * read_as!(u32, buf, 9) as i64),
-                        13 => Duration::seconds(read_as!(u32, buf, 9) as i64),
-                        n => {
-                            return Err(GribError::ParseError(format!("Forecast Time Unit `{}` does not exist.", n)))
-                        },
-                    },
-                    first_surface: Surface {
-                        surface_type: buf[13],
-                        scale_factor: buf[14],
-                        scaled_value: read_as!(u32, buf, 15)
-                    },
-                    second_surface: Surface {
-                        surface_type: buf[19],
-                        scale_factor: buf[20],
-                        scaled_value: read_as!(u32, buf, 21)
-                    }
+                Ok(Product::Product0(Product0::parse(&buf)?))
+            },
+            1 => {
+                // 4.0 fields, then the ensemble descriptors (octets 35-37).
+                let base = Product0::parse(&buf)?;
+                Ok(Product::Product1(Product1 {
+                    base,
+                    ensemble_type: buf[25],
+                    perturbation_number: buf[26],
+                    number_of_forecasts: buf[27],
+                }))
+            },
+            8 => {
+                // 4.0 fields, the end of the overall time interval (octets
+                // 35-41), then the first statistical-process block (octets
+                // 47-53); the leading time-range count and missing-value total
+                // in between are not retained.
+                let base = Product0::parse(&buf)?;
+                Ok(Product::Product8(Product8 {
+                    base,
+                    end_of_interval: IntervalEnd::parse(&buf, 25),
+                    statistical_process: buf[37],
+                    time_increment_type: buf[38],
+                    time_range_unit: buf[39],
+                    time_range_length: read_as!(u32, buf, 40),
+                }))
+            },
+            11 => {
+                // 4.1 ensemble descriptors followed by the 4.8 time-interval and
+                // statistical-process block, shifted by the three ensemble octets.
+                let base = Product0::parse(&buf)?;
+                Ok(Product::Product11(Product11 {
+                    base,
+                    ensemble_type: buf[25],
+                    perturbation_number: buf[26],
+                    number_of_forecasts: buf[27],
+                    end_of_interval: IntervalEnd::parse(&buf, 28),
+                    statistical_process: buf[40],
+                    time_increment_type: buf[41],
+                    time_range_unit: buf[42],
+                    time_range_length: read_as!(u32, buf, 43),
                 }))
             },
             _ => {
@@ -65,6 +74,106 @@ impl Product {
             }
         }
     }
+
+    /// The analysis/forecast core shared by every supported template, or `None`
+    /// for [`Product::Unknown`]. Lets the providers read grid-identifying
+    /// metadata (parameter, surface) without matching on each template.
+    pub(crate) fn base(&self) -> Option<&Product0> {
+        match self {
+            Product::Product0(product) => Some(product),
+            Product::Product1(product) => Some(&product.base),
+            Product::Product8(product) => Some(&product.base),
+            Product::Product11(product) => Some(&product.base),
+            Product::Unknown(_) => None,
+        }
+    }
+
+    /// Ensemble perturbation number for the individual-member templates (4.1 and
+    /// 4.11), or `None` for a non-ensemble product. Lets the providers pick the
+    /// control run apart from a given member.
+    pub(crate) fn perturbation_number(&self) -> Option<u8> {
+        match self {
+            Product::Product1(product) => Some(product.perturbation_number),
+            Product::Product11(product) => Some(product.perturbation_number),
+            _ => None,
+        }
+    }
+
+    /// Statistical-process type (Code Table 4.10) for the time-interval
+    /// templates (4.8 and 4.11), or `None` for an instantaneous product. Lets
+    /// the providers tell an instantaneous field from an average or accumulation.
+    pub(crate) fn statistical_process(&self) -> Option<u8> {
+        match self {
+            Product::Product8(product) => Some(product.statistical_process),
+            Product::Product11(product) => Some(product.statistical_process),
+            _ => None,
+        }
+    }
+
+    /// Encode the product template (octet 10 onwards of Section 4), the inverse
+    /// of `from_template`. The forecast time unit is not retained on parse, so a
+    /// whole number of hours is re-emitted in hours and anything finer in
+    /// minutes (Code Table 4.4).
+    pub(crate) fn encode_template(&self) -> Vec<u8> {
+        match self {
+            Product::Product0(product) => product.encode(),
+            Product::Product1(product) => {
+                let mut buf = product.base.encode();
+                buf.push(product.ensemble_type);
+                buf.push(product.perturbation_number);
+                buf.push(product.number_of_forecasts);
+                buf
+            }
+            Product::Product8(product) => {
+                let mut buf = product.base.encode();
+                product.end_of_interval.encode(&mut buf);
+                // A single time range with no missing values.
+                buf.push(1);
+                buf.extend_from_slice(&0u32.to_be_bytes());
+                buf.push(product.statistical_process);
+                buf.push(product.time_increment_type);
+                buf.push(product.time_range_unit);
+                buf.extend_from_slice(&product.time_range_length.to_be_bytes());
+                // Time increment: unit and length, left unset.
+                buf.push(255);
+                buf.extend_from_slice(&0u32.to_be_bytes());
+                buf
+            }
+            Product::Product11(product) => {
+                let mut buf = product.base.encode();
+                buf.push(product.ensemble_type);
+                buf.push(product.perturbation_number);
+                buf.push(product.number_of_forecasts);
+                product.end_of_interval.encode(&mut buf);
+                buf.push(1);
+                buf.extend_from_slice(&0u32.to_be_bytes());
+                buf.push(product.statistical_process);
+                buf.push(product.time_increment_type);
+                buf.push(product.time_range_unit);
+                buf.extend_from_slice(&product.time_range_length.to_be_bytes());
+                buf.push(255);
+                buf.extend_from_slice(&0u32.to_be_bytes());
+                buf
+            }
+            Product::Unknown(bytes) => bytes.clone(),
+        }
+    }
+}
+
+impl ProductDefinition {
+    /// Encode the Section 4 body (octets 6 onwards), the inverse of
+    /// `read_sect4_body`.
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&self.num_coordinates.to_be_bytes());
+        buf.extend_from_slice(&self.template_number.to_be_bytes());
+        buf.extend_from_slice(&self.product.encode_template());
+        match &self.coordinates {
+            Some(coordinates) => buf.extend_from_slice(coordinates),
+            None => buf.extend(std::iter::repeat(0).take(4 * self.num_coordinates as usize)),
+        }
+        buf
+    }
 }
 
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
@@ -81,9 +190,157 @@ pub struct Product0 {
     second_surface: Surface,
 }
 
+impl Product0 {
+    /// Parse the analysis/forecast core (Template 4.0, octets 10-34) from the
+    /// head of a product template buffer; the ensemble and time-interval
+    /// templates share this prefix.
+    fn parse(buf: &[u8]) -> crate::grib::Result<Product0> {
+        Ok(Product0 {
+            parameter_category: buf[0],
+            parameter_number: buf[1],
+            process_type: buf[2],
+            background_process: buf[3],
+            analysis_process: buf[4],
+            hours: read_as!(u16, buf, 5),
+            minutes: buf[7],
+            forecast_time: match buf[8] {
+                0 => Duration::minutes(read_as!(u32, buf, 9) as i64),
+                1 => Duration::hours(read_as!(u32, buf, 9) as i64),
+                2 => Duration::days(read_as!(u32, buf, 9) as i64),
+                3 => Duration::days(30 * read_as!(u32, buf, 9) as i64),
+                4 => Duration::days(365 * read_as!(u32, buf, 9) as i64),
+                5 => Duration::days(10 * 365 * read_as!(u32, buf, 9) as i64),
+                6 => Duration::days(30 * 365 * read_as!(u32, buf, 9) as i64),
+                7 => Duration::days(100 * 365 * read_as!(u32, buf, 9) as i64),
+                10 => Duration::hours(3 * read_as!(u32, buf, 9) as i64),
+                11 => Duration::hours(6 * read_as!(u32, buf, 9) as i64),
+                12 => Duration::hours(12 * read_as!(u32, buf, 9) as i64),
+                13 => Duration::seconds(read_as!(u32, buf, 9) as i64),
+                n => {
+                    return Err(GribError::ParseError(format!("Forecast Time Unit `{}` does not exist.", n)))
+                },
+            },
+            first_surface: Surface {
+                surface_type: buf[13],
+                scale_factor: buf[14],
+                scaled_value: read_as!(u32, buf, 15)
+            },
+            second_surface: Surface {
+                surface_type: buf[19],
+                scale_factor: buf[20],
+                scaled_value: read_as!(u32, buf, 21)
+            }
+        })
+    }
+
+    /// Encode the 4.0 core (octets 10-34), shared by every supported template.
+    fn encode(&self) -> Vec<u8> {
+        let minutes = self.forecast_time.num_minutes();
+        let (unit, value) = if minutes % 60 == 0 {
+            (1u8, (minutes / 60) as u32)
+        } else {
+            (0u8, minutes as u32)
+        };
+
+        let mut buf = Vec::with_capacity(25);
+        buf.push(self.parameter_category);
+        buf.push(self.parameter_number);
+        buf.push(self.process_type);
+        buf.push(self.background_process);
+        buf.push(self.analysis_process);
+        buf.extend_from_slice(&self.hours.to_be_bytes());
+        buf.push(self.minutes);
+        buf.push(unit);
+        buf.extend_from_slice(&value.to_be_bytes());
+        buf.push(self.first_surface.surface_type);
+        buf.push(self.first_surface.scale_factor);
+        buf.extend_from_slice(&self.first_surface.scaled_value.to_be_bytes());
+        buf.push(self.second_surface.surface_type);
+        buf.push(self.second_surface.scale_factor);
+        buf.extend_from_slice(&self.second_surface.scaled_value.to_be_bytes());
+        buf
+    }
+}
+
+/// Individual ensemble forecast (Product Definition Template 4.1): the 4.0 core
+/// plus the ensemble descriptors.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Product1 {
+    base: Product0,
+    ensemble_type: u8,
+    pub(crate) perturbation_number: u8,
+    number_of_forecasts: u8,
+}
+
+/// Average, accumulation or other statistically-processed field over a time
+/// interval (Product Definition Template 4.8): the 4.0 core, the end of the
+/// overall interval, and the first statistical-process block.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Product8 {
+    base: Product0,
+    end_of_interval: IntervalEnd,
+    pub(crate) statistical_process: u8,
+    time_increment_type: u8,
+    time_range_unit: u8,
+    time_range_length: u32,
+}
+
+/// Individual ensemble forecast over a time interval (Product Definition
+/// Template 4.11): the ensemble descriptors of 4.1 combined with the
+/// time-interval and statistical-process block of 4.8.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Product11 {
+    base: Product0,
+    ensemble_type: u8,
+    pub(crate) perturbation_number: u8,
+    number_of_forecasts: u8,
+    end_of_interval: IntervalEnd,
+    pub(crate) statistical_process: u8,
+    time_increment_type: u8,
+    time_range_unit: u8,
+    time_range_length: u32,
+}
+
+/// End of the overall time interval of a statistically-processed product,
+/// broken down as in the reference-time of Section 1.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct IntervalEnd {
+    pub year: u16,
+    pub month: u8,
+    pub day: u8,
+    pub hour: u8,
+    pub minute: u8,
+    pub second: u8,
+}
+
+impl IntervalEnd {
+    /// Parse the seven-octet interval end starting at `at` within a product
+    /// template buffer.
+    fn parse(buf: &[u8], at: usize) -> IntervalEnd {
+        IntervalEnd {
+            year: read_as!(u16, buf, at),
+            month: buf[at + 2],
+            day: buf[at + 3],
+            hour: buf[at + 4],
+            minute: buf[at + 5],
+            second: buf[at + 6],
+        }
+    }
+
+    /// Append the seven-octet interval end, the inverse of [`parse`](Self::parse).
+    fn encode(&self, buf: &mut Vec<u8>) {
+        buf.extend_from_slice(&self.year.to_be_bytes());
+        buf.push(self.month);
+        buf.push(self.day);
+        buf.push(self.hour);
+        buf.push(self.minute);
+        buf.push(self.second);
+    }
+}
+
 #[derive(Debug, Clone, PartialEq, Eq, Hash)]
 pub struct Surface {
     pub surface_type: u8,
     pub scale_factor: u8,
     pub scaled_value: u32,
-}
\ No newline at end of file
+}
@@ -1,4 +1,4 @@
-use crate::grib::utils::{Buffer, GribInt};
+use crate::grib::utils::{Buffer, GribInt, GribUint};
 use crate::read_as;
 
 pub struct DataRepresentationDefinition {
@@ -11,6 +11,10 @@ pub enum Data {
     Data0(Data0),
     Data2(Data2),
     Data3(Data3),
+    Data4(Data4),
+    Data40(Data40),
+    Data41(Data41),
+    Data200(Data200),
     Unknown(Vec<u8>)
 }
 
@@ -75,11 +79,179 @@ impl Data {
                     spacial_difference_size: buf.read()
                 }))
             }
+            4 => {
+                Ok(Data::Data4(Data4 {
+                    precision: buf.read(),
+                }))
+            }
+            40 => {
+                Ok(Data::Data40(Data40 {
+                    reference_value: buf.read(),
+                    binary_scale_factor: buf.read::<u16>().as_grib_int(),
+                    decimal_scale_factor: buf.read::<u16>().as_grib_int(),
+                    num_bits: buf.read::<u8>() as usize,
+                    values_type: buf.read(),
+                    compression_type: buf.read(),
+                    compression_ratio: buf.read(),
+                }))
+            }
+            41 => {
+                Ok(Data::Data41(Data41 {
+                    reference_value: buf.read(),
+                    binary_scale_factor: buf.read::<u16>().as_grib_int(),
+                    decimal_scale_factor: buf.read::<u16>().as_grib_int(),
+                    num_bits: buf.read::<u8>() as usize,
+                    values_type: buf.read(),
+                }))
+            }
+            200 => {
+                Ok(Data::Data200(Data200 {
+                    num_bits: buf.read::<u8>() as usize,
+                    mv: buf.read(),
+                    mvl: buf.read(),
+                    decimal_scale_factor: buf.read::<u16>().as_grib_int(),
+                }))
+            }
             _ => {
                 Ok(Data::Unknown(buf.bytes))
             }
         }
     }
+
+    /// Turn the raw Data Section payload into the grid of `f64` values,
+    /// dispatching on the packing template. Each template reconstructs the
+    /// values with the WMO unpacking algorithm for its representation; the
+    /// JPEG2000 (5.40) and PNG (5.41) codecs sit behind their cargo features.
+    pub(crate) fn decode(&self, num_points: usize, data_section: &[u8]) -> crate::grib::Result<Box<[f64]>> {
+        use crate::grib::sections::sect7::Grib2DataDecoder;
+        use crate::grib::sections::sect7::complex::GridPointDataComplexPackingDecoder;
+        use crate::grib::sections::sect7::complex_spacial_diff::GridPointDataComplexPackingSpacialDiffDecoder;
+        use crate::grib::sections::sect7::ieee_float::GridPointDataIeeeFloatDecoder;
+        use crate::grib::sections::sect7::run_length::GridPointDataRunLengthDecoder;
+        use crate::grib::sections::sect7::simple::GridPointDataSimplePackingDecoder;
+
+        match self {
+            Data::Data0(_) => GridPointDataSimplePackingDecoder{}.decode(num_points, self, data_section),
+            Data::Data2(_) => GridPointDataComplexPackingDecoder{}.decode(num_points, self, data_section),
+            Data::Data3(_) => GridPointDataComplexPackingSpacialDiffDecoder{}.decode(num_points, self, data_section),
+            Data::Data4(_) => GridPointDataIeeeFloatDecoder{}.decode(num_points, self, data_section),
+            Data::Data40(_) => {
+                #[cfg(feature = "jpeg2000")]
+                {
+                    use crate::grib::sections::sect7::jpeg2000::GridPointDataJpeg2000Decoder;
+                    GridPointDataJpeg2000Decoder{}.decode(num_points, self, data_section)
+                }
+                #[cfg(not(feature = "jpeg2000"))]
+                {
+                    Err(crate::grib::GribError::DecodeError(String::from("JPEG2000 decoder not enabled (build with the `jpeg2000` feature)")))
+                }
+            }
+            Data::Data41(_) => {
+                #[cfg(feature = "png")]
+                {
+                    use crate::grib::sections::sect7::png::GridPointDataPngDecoder;
+                    GridPointDataPngDecoder{}.decode(num_points, self, data_section)
+                }
+                #[cfg(not(feature = "png"))]
+                {
+                    Err(crate::grib::GribError::DecodeError(String::from("PNG decoder not enabled (build with the `png` feature)")))
+                }
+            }
+            Data::Data200(_) => GridPointDataRunLengthDecoder{}.decode(num_points, self, data_section),
+            Data::Unknown(_) => Err(crate::grib::GribError::DecodeError(String::from("Not implemented data decoder"))),
+        }
+    }
+
+    /// Encode the data representation template (octet 12 onwards of Section 5),
+    /// the inverse of `from_template`.
+    pub(crate) fn encode_template(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        match self {
+            Data::Data0(data) => {
+                buf.extend_from_slice(&data.reference_value.to_be_bytes());
+                buf.extend_from_slice(&data.binary_scale_factor.as_grib_uint().to_be_bytes());
+                buf.extend_from_slice(&data.decimal_scale_factor.as_grib_uint().to_be_bytes());
+                buf.push(data.num_bits as u8);
+                buf.push(data.values_type);
+            }
+            Data::Data2(data) => {
+                buf.extend_from_slice(&data.reference_value.to_be_bytes());
+                buf.extend_from_slice(&data.binary_scale_factor.as_grib_uint().to_be_bytes());
+                buf.extend_from_slice(&data.decimal_scale_factor.as_grib_uint().to_be_bytes());
+                buf.push(data.num_bits as u8);
+                buf.push(data.values_type);
+                buf.push(data.group_method);
+                buf.push(data.missing_value);
+                buf.extend_from_slice(&data.missing_substitute_primary.to_be_bytes());
+                buf.extend_from_slice(&data.missing_substitute_secondary.to_be_bytes());
+                buf.push(data.group_definition.num_groups as u8);
+                encode_group_definition(&mut buf, &data.group_definition);
+            }
+            Data::Data3(data) => {
+                buf.extend_from_slice(&data.reference_value.to_be_bytes());
+                buf.extend_from_slice(&data.binary_scale_factor.as_grib_uint().to_be_bytes());
+                buf.extend_from_slice(&data.decimal_scale_factor.as_grib_uint().to_be_bytes());
+                buf.push(data.num_bits as u8);
+                buf.push(data.values_type);
+                buf.push(data.group_method);
+                buf.push(data.missing_value);
+                buf.extend_from_slice(&data.missing_substitute_primary.to_be_bytes());
+                buf.extend_from_slice(&data.missing_substitute_secondary.to_be_bytes());
+                buf.extend_from_slice(&(data.group_definition.num_groups as u32).to_be_bytes());
+                encode_group_definition(&mut buf, &data.group_definition);
+                buf.push(data.spacial_difference_order);
+                buf.push(data.spacial_difference_size);
+            }
+            Data::Data4(data) => {
+                buf.push(data.precision);
+            }
+            Data::Data40(data) => {
+                buf.extend_from_slice(&data.reference_value.to_be_bytes());
+                buf.extend_from_slice(&data.binary_scale_factor.as_grib_uint().to_be_bytes());
+                buf.extend_from_slice(&data.decimal_scale_factor.as_grib_uint().to_be_bytes());
+                buf.push(data.num_bits as u8);
+                buf.push(data.values_type);
+                buf.push(data.compression_type);
+                buf.push(data.compression_ratio);
+            }
+            Data::Data41(data) => {
+                buf.extend_from_slice(&data.reference_value.to_be_bytes());
+                buf.extend_from_slice(&data.binary_scale_factor.as_grib_uint().to_be_bytes());
+                buf.extend_from_slice(&data.decimal_scale_factor.as_grib_uint().to_be_bytes());
+                buf.push(data.num_bits as u8);
+                buf.push(data.values_type);
+            }
+            Data::Data200(data) => {
+                buf.push(data.num_bits as u8);
+                buf.extend_from_slice(&data.mv.to_be_bytes());
+                buf.extend_from_slice(&data.mvl.to_be_bytes());
+                buf.extend_from_slice(&data.decimal_scale_factor.as_grib_uint().to_be_bytes());
+            }
+            Data::Unknown(bytes) => buf.extend_from_slice(bytes),
+        }
+        buf
+    }
+}
+
+fn encode_group_definition(buf: &mut Vec<u8>, group: &GroupDefinition) {
+    buf.push(group.group_widths_reference);
+    buf.push(group.group_widths_num_bits as u8);
+    buf.extend_from_slice(&group.group_lengths_reference.to_be_bytes());
+    buf.push(group.group_lengths_increment);
+    buf.extend_from_slice(&group.group_lengths_last.to_be_bytes());
+    buf.push(group.group_scaled_lengths_num_bits as u8);
+}
+
+impl DataRepresentationDefinition {
+    /// Encode the Section 5 body (octets 6 onwards), the inverse of
+    /// `read_sect5_body`.
+    pub(crate) fn encode(&self) -> Vec<u8> {
+        let mut buf = Vec::new();
+        buf.extend_from_slice(&(self.num_points as u32).to_be_bytes());
+        buf.extend_from_slice(&self.template_number.to_be_bytes());
+        buf.extend_from_slice(&self.data.encode_template());
+        buf
+    }
 }
 
 pub struct Data0 {
@@ -112,6 +284,47 @@ pub struct Data2 {
     pub group_definition: GroupDefinition,
 }
 
+/// Data Representation Template 5.4: grid point data, IEEE floating point.
+pub struct Data4 {
+    /// Precision (Code Table 5.7): 1 = IEEE 32-bit, 2 = IEEE 64-bit.
+    pub precision: u8,
+}
+
+/// Data Representation Template 5.40: grid point data, JPEG2000 compression.
+pub struct Data40 {
+    pub reference_value: f32,
+    pub binary_scale_factor: i16,
+    pub decimal_scale_factor: i16,
+    pub num_bits: usize,
+    pub values_type: u8,
+    /// Type of compression (Code Table 5.40): 0 = lossless, 1 = lossy.
+    pub compression_type: u8,
+    /// Target compression ratio, M:1 (used only for lossy compression).
+    pub compression_ratio: u8,
+}
+
+/// Data Representation Template 5.41: grid point data, PNG compression.
+pub struct Data41 {
+    pub reference_value: f32,
+    pub binary_scale_factor: i16,
+    pub decimal_scale_factor: i16,
+    pub num_bits: usize,
+    pub values_type: u8,
+}
+
+/// Data Representation Template 5.200: run-length packing with levels. Unlike
+/// every other template in this file, 5.200 has no reference-value or
+/// binary-scale-factor octets to parse — the decoded values are level indices
+/// scaled only by `decimal_scale_factor` (see `GridPointDataRunLengthDecoder`).
+pub struct Data200 {
+    pub num_bits: usize,
+    /// Maximum value within the levels present in this field.
+    pub mv: u16,
+    /// Maximum value of a level, i.e. the number of distinct levels.
+    pub mvl: u16,
+    pub decimal_scale_factor: i16,
+}
+
 pub struct Data3 {
     pub reference_value: f32,
     pub binary_scale_factor: i16,
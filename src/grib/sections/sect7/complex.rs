@@ -1,51 +1,90 @@
+use crate::grib::GribError;
 use crate::grib::GribError::ParseError;
-use crate::grib::sections::sect5::{Data, DataRepresentationDefinition};
+use crate::grib::sections::sect5::Data;
 use crate::grib::sections::sect7::{Grib2DataDecoder, groups};
 use crate::grib::utils::{BitwiseIterator, GribInt};
-use std::iter;
-use crate::grib::sections::sect7::simple::SimpleDecoderIterator;
+
+/// Sentinel carried through the i64 decode pipeline for a packed entry equal to
+/// a group's missing-value code (Code Table 5.5, `missing_value` ∈ {1, 2}); it
+/// is turned into `f64::NAN` once the values reach their `f64` form, after any
+/// spatial-difference reconstruction has passed it through untouched.
+pub(crate) const MISSING: i64 = i64::MIN;
+
+/// Apply the overall reference value and the binary/decimal scale factors, the
+/// `f64` tail shared by the complex decoders; a [`MISSING`] sentinel becomes
+/// `NAN` rather than a scaled value.
+pub(crate) fn scale(value: i64, reference_value: f64, binary_scale_factor: i16, decimal_scale_factor: i16) -> f64 {
+    if value == MISSING {
+        return f64::NAN;
+    }
+    let binary_scale = 2_f64.powi(binary_scale_factor as i32);
+    let decimal_scale = 10_f64.powi(-decimal_scale_factor as i32);
+    (reference_value + value as f64 * binary_scale) * decimal_scale
+}
 
 pub(crate) struct GridPointDataComplexPackingDecoder {}
 
 impl Grib2DataDecoder for GridPointDataComplexPackingDecoder {
-    fn decode(&self, data_repr_def: &DataRepresentationDefinition, slice: &Box<[u8]>) -> crate::grib::Result<Box<[f64]>> {
+    fn decode(&self, num_points: usize, data: &Data, slice: &[u8]) -> crate::grib::Result<Box<[f64]>> {
+
+        let (group_iter, groups_num_bytes) = groups::decode(data, slice)?;
 
-        let data = match &data_repr_def.data {
+        let data = match data {
             Data::Data2(data) => data,
             _ => {
                 return Err(ParseError(String::from("Wrong decoder")));
             }
         };
 
-        let (group_iter, groups_num_bytes) = groups::decode(data_repr_def, slice)?;
+        let reference_value = data.reference_value as f64;
+        let decoded: Box<[f64]> =
+            ComplexPackingDecoderIterator::new(&slice[groups_num_bytes..], group_iter, data.missing_value, data.num_bits)
+                .flatten()
+                .map(|v| scale(v, reference_value, data.binary_scale_factor, data.decimal_scale_factor))
+                .collect();
 
-        Ok(
-            SimpleDecoderIterator::new(
-                ComplexPackingDecoderIterator::new(&slice[groups_num_bytes..], group_iter).flatten(),
-                data.reference_value as f64, data.binary_scale_factor, data.decimal_scale_factor
-            ).collect()
-        )
+        if decoded.len() != num_points {
+            return Err(GribError::DecodeError(String::from("Length Mismatch")));
+        }
+
+        Ok(decoded)
     }
 }
 
 pub(crate) struct ComplexPackingDecoderIterator<'a, I: Iterator<Item = (i64, usize, usize)>> {
     slice: &'a [u8],
     groups_iter: I,
+    missing_value: u8,
+    num_bits: usize,
     pos: usize,
     start_offset_num_bits: usize,
 }
 
 impl<'a, I: Iterator<Item = (i64, usize, usize)>> ComplexPackingDecoderIterator<'a, I> {
-    pub(crate) fn new(slice: &'a [u8], groups_iter: I) -> Self {
+    pub(crate) fn new(slice: &'a [u8], groups_iter: I, missing_value: u8, num_bits: usize) -> Self {
         Self {
             slice,
             groups_iter,
+            missing_value,
+            num_bits,
             pos: 0,
             start_offset_num_bits: 0,
         }
     }
 }
 
+/// The all-ones value of a `width`-bit field, i.e. the primary missing-value
+/// code; `None` for the degenerate widths that cannot carry a code (`0`, or the
+/// `>= 64` widths a malformed section might claim, which would overflow the
+/// shift).
+fn all_ones(width: usize) -> Option<u64> {
+    if width == 0 || width >= 64 {
+        None
+    } else {
+        Some((1u64 << width) - 1)
+    }
+}
+
 impl<'a, I: Iterator<Item = (i64, usize, usize)>> Iterator for ComplexPackingDecoderIterator<'a, I> {
     type Item = Vec<i64>;
 
@@ -58,17 +97,49 @@ impl<'a, I: Iterator<Item = (i64, usize, usize)>> Iterator for ComplexPackingDec
                 let width = width as usize;
                 let length = length as usize;
 
-                let total_num_bits = width * length + self.start_offset_num_bits;
-                let (pos_end, offset_num_bits) = (self.pos + total_num_bits / 8, total_num_bits % 8);
-                let offset_byte = if offset_num_bits > 0 { 1 } else { 0 };
-                let group_values =
-                    BitwiseIterator::<u64>::new(&self.slice[self.pos..pos_end + offset_byte], width)
-                        .with_offset(self.start_offset_num_bits)
-                        .take(length)
-                        .map(|v| reference_value + v.as_grib_int())
-                        .collect::<Vec<i64>>();
-                self.pos = pos_end;
-                self.start_offset_num_bits = offset_num_bits;
+                // For a group of width `w` the largest representable raw value
+                // (all ones, `2^w - 1`) flags a primary missing value, the next
+                // one down a secondary missing value; which of the two apply is
+                // driven by the Code Table 5.5 `missing_value` indicator.
+                let missing_value = self.missing_value;
+                let is_missing = |raw: u64, code: Option<u64>| match code {
+                    Some(primary) if missing_value >= 1 && raw == primary => true,
+                    Some(primary) if missing_value == 2 && raw == primary - 1 => true,
+                    _ => false,
+                };
+
+                let group_values = if width == 0 {
+                    // A zero-width group repeats its reference value. When that
+                    // reference itself matches the missing code (in the group
+                    // reference bit width `num_bits`), the whole group is a run
+                    // of missing points.
+                    let value = if is_missing(reference_value as u64, all_ones(self.num_bits)) {
+                        MISSING
+                    } else {
+                        reference_value
+                    };
+                    vec![value; length]
+                } else {
+                    let total_num_bits = width * length + self.start_offset_num_bits;
+                    let (pos_end, offset_num_bits) = (self.pos + total_num_bits / 8, total_num_bits % 8);
+                    let offset_byte = if offset_num_bits > 0 { 1 } else { 0 };
+                    let primary = all_ones(width);
+                    let values =
+                        BitwiseIterator::<u64>::new(&self.slice[self.pos..pos_end + offset_byte], width)
+                            .with_offset(self.start_offset_num_bits)
+                            .take(length)
+                            .map(|v| {
+                                if is_missing(v, primary) {
+                                    MISSING
+                                } else {
+                                    reference_value + v.as_grib_int()
+                                }
+                            })
+                            .collect::<Vec<i64>>();
+                    self.pos = pos_end;
+                    self.start_offset_num_bits = offset_num_bits;
+                    values
+                };
                 Some(group_values)
             }
             _ => None
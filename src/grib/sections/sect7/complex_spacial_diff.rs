@@ -1,95 +1,144 @@
+use crate::grib::GribError;
 use crate::grib::GribError::ParseError;
-use crate::grib::sections::sect5::{Data, DataRepresentationDefinition};
+use crate::grib::sections::sect5::Data;
 use crate::grib::sections::sect7::{Grib2DataDecoder, groups};
-use crate::grib::utils::GribInt;
-use crate::grib::sections::sect7::complex::ComplexPackingDecoderIterator;
-use crate::grib::sections::sect7::simple::SimpleDecoderIterator;
-use crate::read_as;
+use crate::grib::sections::sect7::complex::{ComplexPackingDecoderIterator, MISSING, scale};
 
 pub(crate) struct GridPointDataComplexPackingSpacialDiffDecoder {}
 
 impl Grib2DataDecoder for GridPointDataComplexPackingSpacialDiffDecoder {
-    fn decode(&self, data_repr_def: &DataRepresentationDefinition, slice: &Box<[u8]>) -> crate::grib::Result<Box<[f64]>> {
+    fn decode(&self, num_points: usize, data_enum: &Data, slice: &[u8]) -> crate::grib::Result<Box<[f64]>> {
 
-        let data = match &data_repr_def.data {
+        let data = match data_enum {
             Data::Data3(data) => data,
             _ => {
                 return Err(ParseError(String::from("Wrong decoder")));
             }
         };
 
-        let cpt: usize;
-        let z1 = read_as!(u16, slice, 0).as_grib_int();
-        let (z2, z_min) = {
-            if data.spacial_difference_order == 2 {
-                cpt = 6;
-                (read_as!(u16, slice, 2).as_grib_int(), read_as!(u16, slice, 4).as_grib_int())
-            } else {
-                cpt = 4;
-                (0, read_as!(u16, slice, 2).as_grib_int())
-
-            }
+        // The section begins with the extra descriptors, each `octets` octets
+        // wide and sign-magnitude encoded like every other GRIB integer: the
+        // first value `g0`, then `g1` for second order, then the global minimum
+        // of the differences `g_min`. These are consumed before the group
+        // reference/width/length stream, so the groups offset must skip them.
+        let octets = data.spacial_difference_size as usize;
+        let order = data.spacial_difference_order;
+
+        let g0 = read_sign_magnitude(&slice[0..], octets);
+        let (g1, g_min, cpt) = if order == 2 {
+            (
+                read_sign_magnitude(&slice[octets..], octets),
+                read_sign_magnitude(&slice[2 * octets..], octets),
+                3 * octets,
+            )
+        } else {
+            (0, read_sign_magnitude(&slice[octets..], octets), 2 * octets)
         };
 
-        let (group_iter, groups_num_bytes) = groups::decode(data_repr_def, &slice[cpt..])?;
+        let (group_iter, groups_num_bytes) = groups::decode(data_enum, &slice[cpt..])?;
         let to_skip = groups_num_bytes + cpt;
 
-        //let spdiff_packed_iter = iter::once(z1).chain(iter::once(z2)).chain(ComplexPackingDecoderIterator::new(&slice[to_skip..], group_iter).flatten());
-        let spdiff_packed_iter = ComplexPackingDecoderIterator::new(&slice[to_skip..], group_iter).flatten();
+        // Decode the group values as for plain complex packing, then add back
+        // `g_min` to recover the difference array `X`. A missing-coded entry
+        // stays a `MISSING` sentinel: it takes no part in the differencing and
+        // resolves straight to `NAN` at the end.
+        let diffs = ComplexPackingDecoderIterator::new(&slice[to_skip..], group_iter, data.missing_value, data.num_bits)
+            .flatten()
+            .map(move |v| if v == MISSING { MISSING } else { v + g_min });
+
+        // Undo the spatial differencing over the flattened grid.
+        let spdiff_unpacked = SpatialDiffDecodeIterator::new(diffs, order, g0, g1);
+
+        let reference_value = data.reference_value as f64;
+        let decoded: Box<[f64]> = spdiff_unpacked
+            .map(|v| scale(v, reference_value, data.binary_scale_factor, data.decimal_scale_factor))
+            .collect();
 
-        let spdiff_unpacked = SpatialDiff2ndOrderDecodeIterator::new(spdiff_packed_iter);
+        if decoded.len() != num_points {
+            return Err(GribError::DecodeError(String::from("Length Mismatch")));
+        }
 
-        Ok(
-            SimpleDecoderIterator::new(
-                spdiff_unpacked,
-                data.reference_value as f64, data.binary_scale_factor, data.decimal_scale_factor
-            ).collect()
-        )
+        Ok(decoded)
     }
 }
 
-struct SpatialDiff2ndOrderDecodeIterator<I> {
+/// Read an `octets`-wide big-endian sign-magnitude integer, mirroring
+/// [`GribInt::as_grib_int`](crate::grib::utils::GribInt::as_grib_int) for the
+/// fixed-width types.
+fn read_sign_magnitude(slice: &[u8], octets: usize) -> i64 {
+    let mut raw: u64 = 0;
+    for &byte in &slice[..octets] {
+        raw = (raw << 8) | byte as u64;
+    }
+    let sign_bit = 1u64 << (octets * 8 - 1);
+    if raw & sign_bit != 0 {
+        -((raw & !sign_bit) as i64)
+    } else {
+        raw as i64
+    }
+}
+
+/// Reconstructs `Y` from the difference array `X`:
+/// - first order: `Y[0] = g0`, `Y[i] = X[i] + Y[i-1]`;
+/// - second order: `Y[0] = g0`, `Y[1] = g1`, `Y[i] = X[i] + 2*Y[i-1] - Y[i-2]`.
+///
+/// The leading `order` difference values are placeholders overwritten by the
+/// descriptors, so they are consumed but discarded to keep the stream aligned.
+struct SpatialDiffDecodeIterator<I> {
     iter: I,
+    order: u8,
     count: usize,
+    g0: i64,
+    g1: i64,
     prev1: i64,
     prev2: i64,
 }
 
-impl<I> SpatialDiff2ndOrderDecodeIterator<I> {
-    pub(crate) fn new(iter: I) -> Self {
+impl<I> SpatialDiffDecodeIterator<I> {
+    pub(crate) fn new(iter: I, order: u8, g0: i64, g1: i64) -> Self {
         Self {
             iter,
+            order,
             count: 0,
+            g0,
+            g1,
             prev1: 0,
             prev2: 0,
         }
     }
 }
 
-impl<I: Iterator<Item = i64>> Iterator for SpatialDiff2ndOrderDecodeIterator<I> {
+impl<I: Iterator<Item = i64>> Iterator for SpatialDiffDecodeIterator<I> {
     type Item = i64;
 
     fn next(&mut self) -> Option<i64> {
-        let count = self.count;
+        let x = self.iter.next()?;
+
+        // Missing-coded points sit outside the difference chain: hand the
+        // sentinel straight through and leave the running state untouched so the
+        // neighbours rebuild against the last real value.
+        if x == MISSING {
+            return Some(MISSING);
+        }
+
+        let i = self.count;
         self.count += 1;
 
-        match (count, self.iter.next()) {
-            (_, None) => None,
-            (0, Some(v)) => {
-                self.prev2 = v;
-                Some(v)
+        let y = if self.order == 2 {
+            match i {
+                0 => self.g0,
+                1 => self.g1,
+                _ => x + 2 * self.prev1 - self.prev2,
             }
-            (1, Some(v)) => {
-                self.prev1 = v;
-                Some(v)
+        } else {
+            match i {
+                0 => self.g0,
+                _ => x + self.prev1,
             }
-            (_, Some(v)) => {
-                let v = v + 2 * self.prev1 - self.prev2;
-
-                (self.prev2, self.prev1) = (self.prev1, v);
-                Some(v)
-            },
-        }
+        };
 
+        self.prev2 = self.prev1;
+        self.prev1 = y;
+        Some(y)
     }
 }
@@ -1,11 +1,11 @@
 use std::iter;
 use num::ToPrimitive;
 use crate::grib::GribError::ParseError;
-use crate::grib::sections::sect5::{Data, Data2, Data3, DataRepresentationDefinition};
+use crate::grib::sections::sect5::{Data, Data2, Data3};
 use crate::grib::utils::BitwiseIterator;
 
-pub(crate) fn decode<'a>(data_repr_def: &'a DataRepresentationDefinition, slice: &'a [u8]) -> crate::grib::Result<(impl Iterator<Item = (i64, usize, usize)> + 'a, usize)> {
-    let (num_bits, group_definition) = match &data_repr_def.data {
+pub(crate) fn decode<'a>(data: &'a Data, slice: &'a [u8]) -> crate::grib::Result<(impl Iterator<Item = (i64, usize, usize)> + 'a, usize)> {
+    let (num_bits, group_definition) = match data {
         Data::Data2(Data2 { num_bits, group_definition, .. }) => (num_bits, group_definition),
         Data::Data3(Data3 { num_bits, group_definition, .. }) => (num_bits, group_definition),
         _ => {
@@ -0,0 +1,54 @@
+use crate::grib::GribError;
+use crate::grib::GribError::ParseError;
+use crate::grib::sections::sect5::Data;
+use crate::grib::sections::sect7::Grib2DataDecoder;
+use crate::grib::utils::Buffer;
+
+pub(crate) struct GridPointDataIeeeFloatDecoder {}
+
+impl Grib2DataDecoder for GridPointDataIeeeFloatDecoder {
+    fn decode(&self, num_points: usize, data: &Data, slice: &[u8]) -> crate::grib::Result<Box<[f64]>> {
+
+        let data = match data {
+            Data::Data4(data) => data,
+            _ => {
+                return Err(ParseError(String::from("Wrong decoder")));
+            }
+        };
+
+        let size = match data.precision {
+            1 => std::mem::size_of::<f32>(),
+            2 => std::mem::size_of::<f64>(),
+            p => {
+                return Err(GribError::DecodeError(format!("Unsupported IEEE precision : {}", p)));
+            }
+        };
+
+        if slice.len() < num_points * size {
+            return Err(GribError::DecodeError(String::from("Length Mismatch")));
+        }
+
+        let mut buffer = Buffer::new(slice.to_vec());
+        let mut decoded = Vec::with_capacity(num_points);
+
+        match data.precision {
+            1 => {
+                for _ in 0..num_points {
+                    decoded.push(buffer.read::<f32>() as f64);
+                }
+            }
+            2 => {
+                for _ in 0..num_points {
+                    decoded.push(buffer.read::<f64>());
+                }
+            }
+            _ => unreachable!(),
+        }
+
+        if decoded.len() != num_points {
+            return Err(GribError::DecodeError(String::from("Length Mismatch")));
+        }
+
+        Ok(decoded.into_boxed_slice())
+    }
+}
@@ -0,0 +1,43 @@
+use crate::grib::GribError;
+use crate::grib::GribError::ParseError;
+use crate::grib::sections::sect5::Data;
+use crate::grib::sections::sect7::Grib2DataDecoder;
+use crate::grib::sections::sect7::simple::SimpleDecoderIterator;
+
+pub(crate) struct GridPointDataJpeg2000Decoder {}
+
+impl Grib2DataDecoder for GridPointDataJpeg2000Decoder {
+    fn decode(&self, num_points: usize, data: &Data, slice: &[u8]) -> crate::grib::Result<Box<[f64]>> {
+
+        let data = match data {
+            Data::Data40(data) => data,
+            _ => {
+                return Err(ParseError(String::from("Wrong decoder")));
+            }
+        };
+
+        // Code Table 5.40 only defines lossless (0) and lossy (1) JPEG2000; any
+        // other value means the stream isn't one this decoder can recover.
+        if data.compression_type > 1 {
+            return Err(GribError::DecodeError(format!("Unsupported JPEG2000 compression type : {}", data.compression_type)));
+        }
+
+        // The Section 7 payload is a JPEG2000 codestream whose single-component
+        // samples are the packed integers; decode it through the external codec.
+        let image = jpeg2000::decode::from_memory(slice.as_ref(), jpeg2000::decode::Codec::J2K, &jpeg2000::decode::DecodeConfig::default(), None)
+            .map_err(|e| GribError::DecodeError(format!("JPEG2000: {:?}", e)))?;
+
+        let samples: Vec<u64> = image.into_raw().into_iter().map(|s| s as u64).collect();
+
+        if samples.len() != num_points {
+            return Err(GribError::DecodeError(String::from("Length Mismatch")));
+        }
+
+        Ok(
+            SimpleDecoderIterator::new(
+                samples.into_iter(),
+                data.reference_value as f64, data.binary_scale_factor, data.decimal_scale_factor
+            ).collect()
+        )
+    }
+}
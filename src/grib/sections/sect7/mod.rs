@@ -1,10 +1,16 @@
-use crate::grib::sections::sect5::DataRepresentationDefinition;
+use crate::grib::sections::sect5::Data;
 
 pub(crate) mod simple;
 pub(crate) mod complex;
 mod groups;
 pub(crate) mod complex_spacial_diff;
+pub(crate) mod ieee_float;
+pub(crate) mod run_length;
+#[cfg(feature = "jpeg2000")]
+pub(crate) mod jpeg2000;
+#[cfg(feature = "png")]
+pub(crate) mod png;
 
 pub(crate) trait Grib2DataDecoder {
-    fn decode(&self, data_repr_def: &DataRepresentationDefinition, slice: &Box<[u8]>) -> crate::grib::Result<Box<[f64]>>;
+    fn decode(&self, num_points: usize, data: &Data, slice: &[u8]) -> crate::grib::Result<Box<[f64]>>;
 }
\ No newline at end of file
@@ -0,0 +1,51 @@
+use crate::grib::GribError;
+use crate::grib::GribError::ParseError;
+use crate::grib::sections::sect5::Data;
+use crate::grib::sections::sect7::Grib2DataDecoder;
+use crate::grib::sections::sect7::simple::SimpleDecoderIterator;
+
+pub(crate) struct GridPointDataPngDecoder {}
+
+impl Grib2DataDecoder for GridPointDataPngDecoder {
+    fn decode(&self, num_points: usize, data: &Data, slice: &[u8]) -> crate::grib::Result<Box<[f64]>> {
+
+        let data = match data {
+            Data::Data41(data) => data,
+            _ => {
+                return Err(ParseError(String::from("Wrong decoder")));
+            }
+        };
+
+        // The Section 7 payload is a complete PNG stream whose pixel samples are
+        // the packed integers; decode it to a flat sample buffer.
+        let decoder = png::Decoder::new(slice.as_ref());
+        let mut reader = decoder.read_info().map_err(|e| GribError::DecodeError(format!("PNG: {}", e)))?;
+        let mut buf = vec![0u8; reader.output_buffer_size()];
+        let info = reader.next_frame(&mut buf).map_err(|e| GribError::DecodeError(format!("PNG: {}", e)))?;
+
+        // Template 5.41 packs the values as a single grayscale channel; a colour
+        // image would interleave samples and mean the stream isn't GRIB PNG.
+        if info.color_type != png::ColorType::Grayscale {
+            return Err(GribError::DecodeError(format!("PNG: expected grayscale, got {:?}", info.color_type)));
+        }
+
+        let samples: Vec<u64> = match info.bit_depth {
+            png::BitDepth::Sixteen => buf[..info.buffer_size()]
+                .chunks_exact(2)
+                .map(|b| u16::from_be_bytes([b[0], b[1]]) as u64)
+                .collect(),
+            _ => buf[..info.buffer_size()].iter().map(|b| *b as u64).collect(),
+        };
+
+        if samples.len() != num_points {
+            return Err(GribError::DecodeError(String::from("Length Mismatch")));
+        }
+
+        Ok(
+            SimpleDecoderIterator::new(
+                samples.into_iter(),
+                data.reference_value as f64, data.binary_scale_factor, data.decimal_scale_factor
+            ).collect()
+        )
+    }
+}
@@ -1,16 +1,16 @@
 use num::ToPrimitive;
 use crate::grib::GribError;
 use crate::grib::GribError::ParseError;
-use crate::grib::sections::sect5::{Data, DataRepresentationDefinition};
+use crate::grib::sections::sect5::{Data, Data0};
 use crate::grib::sections::sect7::Grib2DataDecoder;
-use crate::grib::utils::BitwiseIterator;
+use crate::grib::utils::{BitPacker, BitwiseIterator};
 
 pub(crate) struct GridPointDataSimplePackingDecoder {}
 
 impl Grib2DataDecoder for GridPointDataSimplePackingDecoder {
-    fn decode(&self, data_repr_def: &DataRepresentationDefinition, slice: &Box<[u8]>) -> crate::grib::Result<Box<[f64]>> {
+    fn decode(&self, num_points: usize, data: &Data, slice: &[u8]) -> crate::grib::Result<Box<[f64]>> {
 
-        let data = match &data_repr_def.data {
+        let data = match data {
             Data::Data0(data) => data,
             _ => {
                 return Err(ParseError(String::from("Wrong decoder")));
@@ -18,14 +18,14 @@ impl Grib2DataDecoder for GridPointDataSimplePackingDecoder {
         };
 
         if data.num_bits == 0 {
-            let decoded = vec![data.reference_value as f64; data_repr_def.num_points as usize];
+            let decoded = vec![data.reference_value as f64; num_points];
             return Ok(decoded.into_boxed_slice());
         }
 
         let decoder = SimpleDecoderIterator::new(BitwiseIterator::<u32>::new(slice, data.num_bits), data.reference_value as f64, data.binary_scale_factor, data.decimal_scale_factor);
         let decoded: Vec<f64> = decoder.collect();
 
-        if decoded.len() != data_repr_def.num_points {
+        if decoded.len() != num_points {
             return Err(GribError::DecodeError(String::from("Length Mismatch")));
         }
 
@@ -33,6 +33,34 @@ impl Grib2DataDecoder for GridPointDataSimplePackingDecoder {
     }
 }
 
+pub(crate) struct GridPointDataSimplePackingEncoder {}
+
+impl GridPointDataSimplePackingEncoder {
+    /// Pack a grid of `f64` values back into a Section 7 payload with simple
+    /// packing (Data Representation Template 5.0), the inverse of
+    /// [`GridPointDataSimplePackingDecoder`]. Each value is quantised as
+    /// `x = round((value / 10^-D - R) / 2^E)` and written MSB-first in
+    /// `num_bits` bits, matching the bit layout the decoder reads back.
+    pub(crate) fn encode(&self, data: &Data0, values: &[f64]) -> crate::grib::Result<Box<[u8]>> {
+        let reference_value = data.reference_value as f64;
+        let binary_scale = 2_f64.powi(data.binary_scale_factor as i32);
+        let decimal_scale = 10_f64.powi(-data.decimal_scale_factor as i32);
+
+        if data.num_bits == 0 {
+            return Ok(Box::new([]));
+        }
+
+        let mut packer = BitPacker::new();
+        for value in values {
+            let scaled = ((value / decimal_scale - reference_value) / binary_scale).round();
+            let encoded = if scaled < 0.0 { 0 } else { scaled as u64 };
+            packer.push(encoded, data.num_bits);
+        }
+
+        Ok(packer.finish().into_boxed_slice())
+    }
+}
+
 pub(crate) struct SimpleDecoderIterator<I: Iterator<Item = N>, N: ToPrimitive> {
     bitwise_iter: I,
     reference_value: f64,
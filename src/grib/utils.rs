@@ -28,6 +28,31 @@ add_impl_for_ints! {
     (u64, i64),
 }
 
+pub(crate) trait GribUint<U> {
+    fn as_grib_uint(&self) -> U;
+}
+
+macro_rules! add_encode_for_ints {
+    ($(($ty_src:ty, $ty_dst:ty),)*) => ($(
+        impl GribUint<$ty_dst> for $ty_src {
+            fn as_grib_uint(&self) -> $ty_dst {
+                if *self < 0 {
+                    (self.unsigned_abs() as $ty_dst) | ((1 as $ty_dst) << (<$ty_dst>::BITS - 1))
+                } else {
+                    *self as $ty_dst
+                }
+            }
+        }
+    )*);
+}
+
+add_encode_for_ints! {
+    (i8, u8),
+    (i16, u16),
+    (i32, u32),
+    (i64, u64),
+}
+
 
 pub(crate) struct BitwiseIterator<'a, T: 'a + FromPrimitive + Shr<usize, Output = T> + Shl<usize, Output = T> + BitOr<Output = T>> {
     slice: &'a [u8],
@@ -97,6 +122,46 @@ impl<'a, T: 'a + FromPrimitive + Shr<usize, Output = T> + Shl<usize, Output = T>
     }
 }
 
+/// Most-significant-bit-first packer, the inverse of [`BitwiseIterator`]: each
+/// value is appended using its low `num_bits` bits and the final byte is padded
+/// with zero bits so a `BitwiseIterator` over the output reproduces the input.
+pub(crate) struct BitPacker {
+    bytes: Vec<u8>,
+    current: u8,
+    filled: usize,
+}
+
+impl BitPacker {
+    pub(crate) fn new() -> Self {
+        Self {
+            bytes: Vec::new(),
+            current: 0,
+            filled: 0,
+        }
+    }
+
+    pub(crate) fn push(&mut self, value: u64, num_bits: usize) {
+        for i in (0..num_bits).rev() {
+            let bit = ((value >> i) & 1) as u8;
+            self.current = (self.current << 1) | bit;
+            self.filled += 1;
+            if self.filled == 8 {
+                self.bytes.push(self.current);
+                self.current = 0;
+                self.filled = 0;
+            }
+        }
+    }
+
+    pub(crate) fn finish(mut self) -> Vec<u8> {
+        if self.filled > 0 {
+            self.current <<= 8 - self.filled;
+            self.bytes.push(self.current);
+        }
+        self.bytes
+    }
+}
+
 pub(crate) struct Buffer {
     pub(crate) bytes: Vec<u8>,
     pos: usize
@@ -139,3 +204,4 @@ uint_impl! { u16 }
 uint_impl! { u32 }
 
 uint_impl! { f32 }
+uint_impl! { f64 }
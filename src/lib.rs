@@ -11,3 +11,4 @@ pub mod providers;
 mod error;
 mod stamp;
 mod grib;
+mod store;
@@ -1,7 +1,6 @@
 #![feature(hash_drain_filter, async_closure)]
 #![feature(exit_status_error)]
 
-use std::collections::HashMap;
 use structopt::StructOpt;
 
 extern crate log;
@@ -10,15 +9,34 @@ extern crate rocket;
 
 mod api;
 mod config;
+mod config_watcher;
 mod providers;
 mod error;
 mod stamp;
+mod store;
 
 #[derive(Debug, StructOpt)]
 struct Cli {
     /// config file
     #[structopt(long = "config", short = "c", default_value = "config.yaml")]
     config_file: String,
+
+    #[structopt(subcommand)]
+    command: Option<Command>,
+}
+
+#[derive(Debug, StructOpt)]
+enum Command {
+    /// Dump the persistent grid store to a single archive file.
+    Backup {
+        /// Archive to write.
+        archive: String,
+    },
+    /// Reload a store archive produced by `backup`.
+    Restore {
+        /// Archive to read.
+        archive: String,
+    },
 }
 
 #[rocket::main]
@@ -30,24 +48,62 @@ async fn main() -> () {
 
     let args = Cli::from_args();
 
-    let config: config::Config = confy::load_path(std::path::Path::new(&args.config_file)).unwrap();
-
-    let mut winds = HashMap::new();
-    for provider_config in config.providers {
-        match providers::start_provider(&provider_config).await {
-            Ok(Some(status)) => {
-                let name = status.read().await.provider.clone();
-                winds.insert(name, status);
-            },
-            Ok(None) => {},
-            Err(e) => error!("Error starting provider `{:?}` : {:?}", provider_config, e)
+    // The backup/restore subcommands operate on the configured store and exit;
+    // only the default (no subcommand) path launches the server.
+    if let Some(command) = &args.command {
+        if let Err(e) = run_store_command(&args.config_file, command) {
+            error!("{:?}", e);
         }
+        return;
     }
 
-    match api::build().manage(winds).launch().await {
+    // Open the persistent store, if configured, before any provider loads so the
+    // warm grids decoded in a previous run are reused instead of re-downloaded.
+    if let Err(e) = open_store(&args.config_file) {
+        error!("Error opening store : {:?}", e);
+    }
+
+    // The watcher starts every enabled provider and keeps the fleet in sync with
+    // the config file at runtime; it must outlive the server, so keep it bound.
+    let watcher = config_watcher::spawn_config_watcher(args.config_file.clone()).await;
+
+    // Manage the live provider map, not a point-in-time snapshot, so routes see
+    // providers the watcher enables, disables, or restarts after startup.
+    match api::build().manage(watcher.providers()).launch().await {
         Ok(_) => (),
         Err(e) => {
             error!("Error launching server : {:?}", e);
         }
     }
 }
+
+/// The configured store path, or `None` when the config omits `store`. A parse
+/// failure is surfaced as an error rather than silently disabling persistence.
+fn store_path(config_file: &str) -> anyhow::Result<Option<String>> {
+    let config: config::Config = confy::load_path(std::path::Path::new(config_file))?;
+    Ok(config.store)
+}
+
+/// Install the process-wide store from config, a no-op when none is configured.
+fn open_store(config_file: &str) -> anyhow::Result<()> {
+    if let Some(path) = store_path(config_file)? {
+        store::init_global(store::Store::open(path)?);
+    }
+    Ok(())
+}
+
+fn run_store_command(config_file: &str, command: &Command) -> anyhow::Result<()> {
+    let path = store_path(config_file)?.ok_or_else(|| anyhow::anyhow!("no `store` configured in {}", config_file))?;
+    let store = store::Store::open(path)?;
+    match command {
+        Command::Backup { archive } => {
+            let count = store.backup(archive)?;
+            info!("Backed up {} stamps to {}", count, archive);
+        }
+        Command::Restore { archive } => {
+            let count = store.restore(archive)?;
+            info!("Restored {} stamps from {}", count, archive);
+        }
+    }
+    Ok(())
+}
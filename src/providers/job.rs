@@ -0,0 +1,57 @@
+use serde::{Deserialize, Serialize};
+use crate::stamp::RefTime;
+
+/// State of a single per-stamp download task.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Serialize, Deserialize)]
+pub enum TaskState {
+    Pending,
+    Running,
+    Done,
+    Failed,
+}
+
+/// One forecast hour to download within a [`JobReport`].
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct StampTask {
+    pub forecast_hour: u16,
+    pub state: TaskState,
+}
+
+/// The set of download tasks for a single ref-time.
+///
+/// A job is never persisted on its own: the authoritative state is what already
+/// exists in `Storage`, so a job interrupted by a crash or a `cancel()` is
+/// reconstructed by re-deriving the remaining stamps from storage on the next
+/// run. This report exposes that reconstructed state for structured progress.
+#[derive(Clone, Debug, Serialize, Deserialize)]
+pub struct JobReport {
+    pub ref_time: RefTime,
+    pub tasks: Vec<StampTask>,
+}
+
+impl JobReport {
+    pub(crate) fn new(ref_time: RefTime, forecast_hours: impl IntoIterator<Item = u16>) -> Self {
+        Self {
+            ref_time,
+            tasks: forecast_hours
+                .into_iter()
+                .map(|forecast_hour| StampTask { forecast_hour, state: TaskState::Pending })
+                .collect(),
+        }
+    }
+
+    pub(crate) fn mark(&mut self, forecast_hour: u16, state: TaskState) {
+        if let Some(task) = self.tasks.iter_mut().find(|t| t.forecast_hour == forecast_hour) {
+            task.state = state;
+        }
+    }
+
+    /// Percentage of tasks that have reached a terminal `Done` state.
+    pub(crate) fn percent(&self) -> u8 {
+        if self.tasks.is_empty() {
+            return 100;
+        }
+        let done = self.tasks.iter().filter(|t| t.state == TaskState::Done).count();
+        (100 * done / self.tasks.len()) as u8
+    }
+}
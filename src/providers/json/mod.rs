@@ -1,19 +1,33 @@
-use std::fs::File;
-use std::io::BufReader;
-use anyhow::Result;
-use serde::Deserialize;
-use std::path::PathBuf;
+use serde::{Deserialize, Deserializer, Serialize, Serializer};
+use std::path::{Path, PathBuf};
 use crate::providers::Wind;
-use crate::stamp::Stamp;
 
-#[derive(Deserialize, Debug)]
+#[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Message {
     pub header: Header,
+    #[serde(with = "grid_values")]
     pub data: Box<[f64]>,
 }
 
-#[derive(Deserialize, Debug)]
+/// (De)serialize a grid as JSON numbers, but carry missing values — the
+/// `f64::NAN`s the complex-packing decoders emit for masked points — as JSON
+/// `null` rather than letting `serde_json` reject the non-finite float. Finite
+/// values round-trip unchanged, so existing blobs keep deserializing.
+mod grid_values {
+    use super::*;
+
+    pub(super) fn serialize<S: Serializer>(data: &[f64], serializer: S) -> Result<S::Ok, S::Error> {
+        serializer.collect_seq(data.iter().map(|v| if v.is_finite() { Some(*v) } else { None }))
+    }
+
+    pub(super) fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<Box<[f64]>, D::Error> {
+        let values = Vec::<Option<f64>>::deserialize(deserializer)?;
+        Ok(values.into_iter().map(|v| v.unwrap_or(f64::NAN)).collect())
+    }
+}
+
+#[derive(Deserialize, Serialize, Debug)]
 #[serde(rename_all = "camelCase")]
 pub struct Header {
     pub discipline: u8,
@@ -29,22 +43,63 @@ pub struct Header {
     pub dy: f64,
 }
 
-pub async fn load_all(stamps: &Vec<Stamp>) -> Result<Vec<Wind>> {
-
-    let mut res = Vec::new();
-    for stamp in stamps {
-        res.push(load(stamp.file_name().into()).await?);
+/// Load the decoded binary cache sitting next to the Local JSON blob for
+/// `file_name`, but only when it is at least as new as the JSON source it was
+/// derived from. Returns `None` when the cache is missing, stale or unreadable,
+/// so the caller falls back to decoding the JSON. The live read path consults
+/// this before the `serde_json` decode to accelerate a warm cold start.
+pub(crate) fn load_cached(dir: &str, file_name: &str) -> Option<Wind> {
+    let base = Path::new(dir).join(file_name);
+    let source = StoredBlock::resolve(&base);
+    let cache = PathBuf::from(format!("{}.bin", base.display()));
+    if cache_is_fresh(&cache, source.path()) {
+        return Wind::load_cache(&cache).ok();
     }
+    None
+}
 
-    Ok(res)
+/// Write the decoded binary cache next to the Local JSON blob so the next cold
+/// start is warm. A write failure is non-fatal and simply leaves the next read
+/// to decode the JSON again.
+pub(crate) fn store_cache(dir: &str, file_name: &str, wind: &Wind) {
+    let cache = Path::new(dir).join(format!("{}.bin", file_name));
+    let _ = wind.write_cache(cache);
 }
 
-pub async fn load(json_filename: PathBuf) -> Result<Wind> {
+/// Whether `cache` exists and is no older than `source`, i.e. safe to read back
+/// in place of re-decoding the JSON.
+fn cache_is_fresh(cache: &Path, source: &Path) -> bool {
+    let cache_mtime = match std::fs::metadata(cache).and_then(|m| m.modified()) {
+        Ok(mtime) => mtime,
+        Err(_) => return false,
+    };
+    match std::fs::metadata(source).and_then(|m| m.modified()) {
+        Ok(source_mtime) => cache_mtime >= source_mtime,
+        Err(_) => false,
+    }
+}
 
-    let f = File::open(&json_filename)?;
-    let f = BufReader::new(f);
+/// On-disk variant of a stored JSON blob: the compressed `.zst` sibling when it
+/// exists, otherwise the plain file.
+enum StoredBlock {
+    Plain(PathBuf),
+    Compressed(PathBuf),
+}
 
-    let messages: Vec<Message> = serde_json::from_reader(f)?;
+impl StoredBlock {
+    fn resolve(base: &Path) -> Self {
+        let compressed = PathBuf::from(format!("{}.zst", base.display()));
+        if compressed.exists() {
+            StoredBlock::Compressed(compressed)
+        } else {
+            StoredBlock::Plain(base.to_path_buf())
+        }
+    }
 
-    Ok(messages.try_into()?)
+    /// The on-disk path this variant resolved to.
+    fn path(&self) -> &Path {
+        match self {
+            StoredBlock::Plain(path) | StoredBlock::Compressed(path) => path,
+        }
+    }
 }
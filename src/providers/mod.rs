@@ -1,5 +1,7 @@
 pub mod noaa;
+pub(crate) mod job;
 pub(crate) mod json;
+pub(crate) mod retry;
 pub mod zezo;
 
 use std::cmp::Ordering;
@@ -7,6 +9,7 @@ use chrono::{DateTime, Duration, Utc};
 use std::collections::BTreeMap;
 use std::fmt::{Debug, Display, Formatter};
 use std::future::Future;
+use std::io::Read;
 use std::ops::Sub;
 use std::path::{PathBuf};
 use std::sync::Arc;
@@ -19,7 +22,10 @@ use crate::config::{MeteofranceProviderConfig, NoaaProviderConfig, ProviderConfi
 use crate::error;
 
 use crate::error::{Error, Result};
-use crate::providers::json::Message;
+use crate::grib;
+use crate::grib::sections::sect3::Grid;
+use crate::providers::job::{JobReport, TaskState};
+use crate::providers::json::{Header, Message};
 use crate::providers::noaa::Noaa;
 use crate::providers::zezo::Zezo;
 use crate::stamp::{ForecastTime, ForecastTimeSpec, RefTime, Stamp};
@@ -30,6 +36,9 @@ pub struct Wind {
   pub delta_lat: f64,
   pub delta_lon: f64,
   n_lat: usize,
+  /// Number of distinct longitude samples. Each row of `u`/`v` must be
+  /// `n_lon + 1` long, the last cell duplicating the first so `sample` can
+  /// wrap without a bounds check — see [`build_grid`].
   n_lon: usize,
   pub u: Box<[Box<[f64]>]>,
   pub v: Box<[Box<[f64]>]>,
@@ -48,6 +57,144 @@ impl Debug for Wind {
   }
 }
 
+impl Wind {
+  /// Bilinearly interpolate `(u, v)` at an arbitrary latitude/longitude.
+  ///
+  /// Longitude wraps around the globe through the continuous column that
+  /// [`build_grid`] appends; latitudes outside the grid band are clamped to the
+  /// nearest row, so sampling at or beyond the poles returns the edge row.
+  pub fn uv_at(&self, lat: f64, lon: f64) -> (f64, f64) {
+    (self.sample(&self.u, lat, lon), self.sample(&self.v, lat, lon))
+  }
+
+  fn sample(&self, grid: &[Box<[f64]>], lat: f64, lon: f64) -> f64 {
+    // Fractional row, clamped into the latitude band.
+    let fy = ((lat - self.lat0) / self.delta_lat).max(0.0).min((self.n_lat - 1) as f64);
+    let i0 = fy.floor() as usize;
+    let i1 = (i0 + 1).min(self.n_lat - 1);
+    let ty = fy - i0 as f64;
+
+    // Fractional column, wrapped into `[0, n_lon)`; the grid carries an extra
+    // wrap column at index `n_lon` so `j0 + 1` is always in range.
+    let wrapped = ((lon - self.lon0) / self.delta_lon).rem_euclid(self.n_lon as f64);
+    let j0 = wrapped.floor() as usize;
+    let j1 = j0 + 1;
+    let tx = wrapped - j0 as f64;
+
+    let top = grid[i0][j0] + (grid[i0][j1] - grid[i0][j0]) * tx;
+    let bottom = grid[i1][j0] + (grid[i1][j1] - grid[i1][j0]) * tx;
+    top + (bottom - top) * ty
+  }
+
+  /// Wind speed (same unit as the grid, typically m/s) at a point.
+  pub fn speed_at(&self, lat: f64, lon: f64) -> f64 {
+    let (u, v) = self.uv_at(lat, lon);
+    (u * u + v * v).sqrt()
+  }
+
+  /// Meteorological wind direction in degrees: the bearing the wind blows
+  /// *from*, measured clockwise from true north.
+  pub fn direction_at(&self, lat: f64, lon: f64) -> f64 {
+    let (u, v) = self.uv_at(lat, lon);
+    (-u).atan2(-v).to_degrees().rem_euclid(360.0)
+  }
+
+  /// Write a compact, self-describing binary cache: a fixed geometry header
+  /// (`WND1` magic, grid dimensions and origin/step) followed by the raw
+  /// little-endian `f64` `u` then `v` grids row by row. Reading it back is a
+  /// handful of `memcpy`s, avoiding the `serde_json` decode of the decoded grid
+  /// on every cold start.
+  pub(crate) fn write_cache<P: AsRef<std::path::Path>>(&self, path: P) -> anyhow::Result<()> {
+    std::fs::write(path, self.to_cache_bytes())?;
+
+    Ok(())
+  }
+
+  /// Serialize the grid into the self-describing binary cache format, so the
+  /// same bytes can be written to a file or handed to the embedded
+  /// [`Store`](crate::store::Store) without a second encoding path.
+  pub(crate) fn to_cache_bytes(&self) -> Vec<u8> {
+    let row_len = self.u.first().map(|row| row.len()).unwrap_or(0);
+
+    let mut buf = Vec::with_capacity(4 + 7 * 8 + 2 * self.n_lat * row_len * 8);
+    buf.extend_from_slice(b"WND1");
+    buf.extend_from_slice(&(self.n_lat as u64).to_le_bytes());
+    buf.extend_from_slice(&(self.n_lon as u64).to_le_bytes());
+    buf.extend_from_slice(&(row_len as u64).to_le_bytes());
+    buf.extend_from_slice(&self.lat0.to_le_bytes());
+    buf.extend_from_slice(&self.lon0.to_le_bytes());
+    buf.extend_from_slice(&self.delta_lat.to_le_bytes());
+    buf.extend_from_slice(&self.delta_lon.to_le_bytes());
+
+    for grid in [&self.u, &self.v] {
+      for row in grid.iter() {
+        for value in row.iter() {
+          buf.extend_from_slice(&value.to_le_bytes());
+        }
+      }
+    }
+
+    buf
+  }
+
+  /// Reconstruct a `Wind` from a cache written by [`write_cache`](Self::write_cache).
+  /// A bad magic or a truncated payload is an error rather than a silently
+  /// wrong grid.
+  pub(crate) fn load_cache<P: AsRef<std::path::Path>>(path: P) -> anyhow::Result<Wind> {
+    Self::from_cache_bytes(&std::fs::read(path)?)
+  }
+
+  /// Decode a grid from the binary cache format, the read-side counterpart of
+  /// [`to_cache_bytes`](Self::to_cache_bytes).
+  pub(crate) fn from_cache_bytes(bytes: &[u8]) -> anyhow::Result<Wind> {
+    if bytes.len() < 4 + 7 * 8 || &bytes[0..4] != b"WND1" {
+      return Err(anyhow!("Not a wind cache"));
+    }
+
+    let mut pos = 4;
+    let read_u64 = |bytes: &[u8], pos: &mut usize| {
+      let v = u64::from_le_bytes(bytes[*pos..*pos + 8].try_into().unwrap());
+      *pos += 8;
+      v
+    };
+    let n_lat = read_u64(&bytes, &mut pos) as usize;
+    let n_lon = read_u64(&bytes, &mut pos) as usize;
+    let row_len = read_u64(&bytes, &mut pos) as usize;
+
+    let read_f64 = |bytes: &[u8], pos: &mut usize| {
+      let v = f64::from_le_bytes(bytes[*pos..*pos + 8].try_into().unwrap());
+      *pos += 8;
+      v
+    };
+    let lat0 = read_f64(&bytes, &mut pos);
+    let lon0 = read_f64(&bytes, &mut pos);
+    let delta_lat = read_f64(&bytes, &mut pos);
+    let delta_lon = read_f64(&bytes, &mut pos);
+
+    let expected = 2usize.saturating_mul(n_lat).saturating_mul(row_len).saturating_mul(8);
+    if bytes.len() - pos != expected {
+      return Err(anyhow!("Wind cache size mismatch"));
+    }
+
+    let read_grid = |bytes: &[u8], pos: &mut usize| {
+      let mut grid = Vec::with_capacity(n_lat);
+      for _ in 0..n_lat {
+        let mut row = Vec::with_capacity(row_len);
+        for _ in 0..row_len {
+          row.push(f64::from_le_bytes(bytes[*pos..*pos + 8].try_into().unwrap()));
+          *pos += 8;
+        }
+        grid.push(row.into_boxed_slice());
+      }
+      grid.into_boxed_slice()
+    };
+    let u = read_grid(&bytes, &mut pos);
+    let v = read_grid(&bytes, &mut pos);
+
+    Ok(Wind { lat0, lon0, delta_lat, delta_lon, n_lat, n_lon, u, v })
+  }
+}
+
 impl TryFrom<Vec<Message>> for Wind {
   type Error = anyhow::Error;
 
@@ -110,6 +257,15 @@ impl TryFrom<Vec<Message>> for Wind {
 }
 
 pub async fn start_provider(provider_config: &ProviderConfig) -> Result<Option<Winds>> {
+  // Detach the background task (tokio keeps it running on drop), preserving the
+  // fire-and-forget behaviour callers that don't manage lifecycles rely on.
+  Ok(start_provider_task(provider_config).await?.map(|(winds, _handle)| winds))
+}
+
+/// Like [`start_provider`] but also hands back the `JoinHandle` of the spawned
+/// background task so a supervisor (the config watcher) can abort it when the
+/// provider is disabled or reconfigured.
+pub async fn start_provider_task(provider_config: &ProviderConfig) -> Result<Option<(Winds, tokio::task::JoinHandle<()>)>> {
 
   match provider_config {
     ProviderConfig::Noaa(NoaaProviderConfig { enabled: false, .. }) => {
@@ -120,10 +276,10 @@ pub async fn start_provider(provider_config: &ProviderConfig) -> Result<Option<W
       let noaa = Noaa::from_config(config)?;
       let winds = noaa.load(true, false).await?;
       noaa.init(config.init).await;
-      tokio::spawn(async move {
+      let handle = tokio::spawn(async move {
         noaa.start().await;
       });
-      Ok(Some(winds))
+      Ok(Some((winds, handle)))
     },
     ProviderConfig::Meteofrance(MeteofranceProviderConfig { enabled: false, .. }) => {
       Ok(None)
@@ -139,10 +295,10 @@ pub async fn start_provider(provider_config: &ProviderConfig) -> Result<Option<W
       let zezo = Zezo::from_config(config)?;
       let winds = zezo.load(true, false).await?;
       zezo.init(config.init).await;
-      tokio::spawn(async move {
+      let handle = tokio::spawn(async move {
         zezo.start().await;
       });
-      Ok(Some(winds))
+      Ok(Some((winds, handle)))
     },
   }
 }
@@ -166,11 +322,12 @@ impl JsonProvider for dyn Provider + Sync {
     let status = self.status().clone();
     let status = status.read().await;
 
+    let storage = self.jsons_storage();
     let keys: Vec<_> = status.forecasts.keys().cloned().collect();
 
     if keys[0] > datetime {
       return Ok((
-        Some(json::load_all(status.forecasts.get(&keys[0]).unwrap()).await?),
+        Some(load_all_stored(&storage, status.forecasts.get(&keys[0]).unwrap()).await?),
         None,
         0.0
         ))
@@ -181,15 +338,15 @@ impl JsonProvider for dyn Provider + Sync {
         let h = keys[i-1].sub(datetime).num_seconds() as f64;
         let delta = keys[i-1].sub(keys[i]).num_seconds() as f64;
         return Ok((
-          Some(json::load_all(status.forecasts.get(&keys[i-1]).unwrap()).await?),
-          Some(json::load_all(status.forecasts.get(&keys[i]).unwrap()).await?),
+          Some(load_all_stored(&storage, status.forecasts.get(&keys[i-1]).unwrap()).await?),
+          Some(load_all_stored(&storage, status.forecasts.get(&keys[i]).unwrap()).await?),
           h / delta
         ));
       }
     }
 
     Ok((
-      Some(json::load_all(status.forecasts.get(keys.last().ok_or(error::Error::Error())?).unwrap()).await?),
+      Some(load_all_stored(&storage, status.forecasts.get(keys.last().ok_or(error::Error::Error())?).unwrap()).await?),
       None,
       0.0
     ))
@@ -197,6 +354,53 @@ impl JsonProvider for dyn Provider + Sync {
 
 }
 
+/// Load the winds for a set of stamps through `Storage`, so the read path works
+/// identically against a local directory or a remote bucket.
+async fn load_all_stored(storage: &Storage, stamps: &Vec<Stamp>) -> Result<Vec<Wind>> {
+  let mut res = Vec::with_capacity(stamps.len());
+  for stamp in stamps {
+    // Prefer the embedded store so warm grids load without touching the backend
+    // or re-parsing JSON; on a miss fall back to `Storage` and seed the store so
+    // the next read (and the next restart) is warm.
+    if let Some(store) = crate::store::global() {
+      let namespace = storage.to_string();
+      if let Ok(Some(bytes)) = store.get(&namespace, stamp) {
+        if let Ok(wind) = Wind::from_cache_bytes(&bytes) {
+          res.push(wind);
+          continue;
+        }
+      }
+
+      let wind = decode_stamp(storage, stamp).await?;
+      if let Err(e) = store.put(&namespace, stamp, &wind.to_cache_bytes()) {
+        warn!("Unable to warm store for {} : {}", stamp.file_name(), e);
+      }
+      res.push(wind);
+    } else {
+      res.push(decode_stamp(storage, stamp).await?);
+    }
+  }
+  Ok(res)
+}
+
+/// Decode a single stamp's grid. For a `Local` backend a fresh `.bin` sidecar
+/// next to the JSON blob is preferred over re-decoding it, and refreshed after a
+/// miss; the JSON fall-back still goes through the integrity-verified
+/// `Storage::get`. Remote backends read straight through `Storage`.
+async fn decode_stamp(storage: &Storage, stamp: &Stamp) -> Result<Wind> {
+  // The sidecar sits next to a single on-disk JSON blob, so it only applies to a
+  // non-split `Local` backend; split payloads have no such sibling to key off.
+  if let Storage::Local { dir, split: None, .. } = storage {
+    if let Some(wind) = json::load_cached(dir, &stamp.file_name()) {
+      return Ok(wind);
+    }
+    let wind: Wind = storage.get(stamp.file_name()).await?.try_into()?;
+    json::store_cache(dir, &stamp.file_name(), &wind);
+    return Ok(wind);
+  }
+  Ok(storage.get(stamp.file_name()).await?.try_into()?)
+}
+
 fn build_grid(data: Box<[f64]>, nb_lat: usize, nb_lon: usize) -> Box<[Box<[f64]>]> {
 
   let is_continuous = true;
@@ -238,9 +442,43 @@ pub trait Provider {
 
   fn current_ref_time(&self) -> RefTime;
 
+  /// Maximum number of stamps downloaded concurrently by the job scheduler.
+  fn concurrency(&self) -> usize {
+    4
+  }
+
+  async fn pause(&self) {
+    info!("{} - Pause downloads", self.id());
+    self.status().set_paused(true).await;
+  }
+
+  async fn resume(&self) {
+    info!("{} - Resume downloads", self.id());
+    self.status().set_paused(false).await;
+  }
+
+  async fn cancel(&self) {
+    info!("{} - Cancel current download job", self.id());
+    self.status().cancel().await;
+  }
+
+  /// Structured, per-stamp progress for the current ref-time, or `None` when no
+  /// job has run yet.
+  async fn progress_report(&self) -> Option<JobReport> {
+    self.status().report().await
+  }
+
   async fn load(&self, delete: bool, load: bool) -> Result<Winds> {
     info!("{} - Load provider", self.id());
 
+    // Report how many stamps for the current ref-time are already warm in the
+    // persistent store, so a restart's reuse is visible in the logs.
+    if let Some(store) = crate::store::global() {
+      if let Ok(warm) = store.range(&self.jsons_storage().to_string(), &self.current_ref_time()) {
+        debug!("{} - {} stamps warm in store for {}", self.id(), warm.len(), self.current_ref_time());
+      }
+    }
+
     let mut stamps = self.jsons_storage().list().await?;
 
     stamps.sort_by(|a, b| {
@@ -287,19 +525,17 @@ pub trait Provider {
   async fn refresh(&self) -> Result<()> {
     debug!("{} - Refresh provider", self.id());
 
+    // Snapshot the existing keys once, outside the write lock, so the retain
+    // pass below never blocks on backend latency while holding the lock.
+    let existing = self.jsons_storage().list_keys().await.unwrap_or_default();
+
     {
       let status = self.status();
       let mut status = status.write().await;
 
       // Remove forecasts for which files were deleted
-      let storage = self.jsons_storage().clone();
       status.forecasts.retain(|_, stamps| {
-        for stamp in stamps {
-          if !storage.exists_blocking(stamp.file_name()).unwrap_or(false) {
-            return false;
-          }
-        }
-        true
+        stamps.iter().all(|stamp| existing.contains(&stamp.file_name()))
       });
     }
 
@@ -362,7 +598,17 @@ pub trait Provider {
 
   async fn download_at(&self, ref_time: RefTime);
 
+  /// Whether GRIB files are decoded natively instead of via `grib2json`.
+  fn native(&self) -> bool {
+    false
+  }
+
   async fn on_file_downloaded(&self, grib_path: PathBuf, stamp: &Stamp) -> Result<()> {
+
+    if self.native() {
+      return self.on_file_downloaded_native(grib_path, stamp).await;
+    }
+
     debug!("{} - Convert grib `{}` to json", self.id(), stamp);
 
     let file = NamedTempFile::new()?;
@@ -385,7 +631,10 @@ pub trait Provider {
     match output.status.exit_ok() {
       Ok(()) => {
 
-        self.jsons_storage().save(&json_path, stamp.file_name()).await?;
+        let (changed, hash) = self.jsons_storage().save_if_changed(&json_path, stamp.file_name()).await?;
+        if !changed {
+          debug!("{} - `{}` unchanged, kept existing data ({}...)", self.id(), stamp, &hash[..12.min(hash.len())]);
+        }
 
         std::fs::remove_file(&json_path).unwrap_or_default();
 
@@ -398,6 +647,28 @@ pub trait Provider {
     }
   }
 
+  /// Native replacement for the `grib2json` fork/exec: decode the GRIB message
+  /// directly with the in-crate decoders, emit the same JSON the Java tool
+  /// produced, and persist it through `Storage`.
+  async fn on_file_downloaded_native(&self, grib_path: PathBuf, stamp: &Stamp) -> Result<()> {
+    debug!("{} - Decode grib `{}` natively", self.id(), stamp);
+
+    let messages = grib_to_messages(&grib_path)?;
+
+    let file = NamedTempFile::new()?;
+    let (json_file, json_path) = file.into_parts();
+    serde_json::to_writer(json_file, &messages).map_err(anyhow::Error::from)?;
+
+    let (changed, hash) = self.jsons_storage().save_if_changed(&json_path, stamp.file_name()).await?;
+    if !changed {
+      debug!("{} - `{}` unchanged, kept existing data ({}...)", self.id(), stamp, &hash[..12.min(hash.len())]);
+    }
+
+    std::fs::remove_file(&json_path).unwrap_or_default();
+
+    Ok(())
+  }
+
   async fn on_stamp_downloaded(&self, delete: bool, load: bool, stamp: Stamp) -> Result<()> {
 
     if delete {
@@ -415,6 +686,8 @@ pub trait Provider {
     self.status().set_last(stamp.ref_time, stamp.forecast_hour(), self.max_forecast_hour()).await;
 
     let mut stamp = stamp;
+    // Record the stored content hash so callers can detect real data changes.
+    stamp.hash = self.jsons_storage().stored_hash(&stamp.file_name()).await.ok().flatten();
     if load {
       debug!("Load `{}` {}", stamp, stamp.file_name());
       stamp.wind  = Some(Arc::new(self.load_stamp(&stamp).await?.try_into()?));
@@ -431,6 +704,17 @@ pub trait Provider {
 
   async fn clean(&self) {
 
+    // Evict stamps from the persistent store on the same schedule as the
+    // in-memory forecasts, so the on-disk cache does not outlive the grids it
+    // mirrors.
+    if let Some(store) = crate::store::global() {
+      match store.evict_expired(Utc::now(), Duration::hours(3)) {
+        Ok(n) if n > 0 => debug!("{} - Evicted {} expired stamps from store", self.id(), n),
+        Ok(_) => {},
+        Err(e) => error!("{} - Error evicting expired stamps from store : {}", self.id(), e),
+      }
+    }
+
     let status = self.status();
     let mut status = status.write().await;
 
@@ -447,6 +731,148 @@ pub trait Provider {
 
 }
 
+/// Decode every message of a GRIB file into the JSON shape the `grib2json`
+/// tool emitted, so the rest of the pipeline is oblivious to which decoder ran.
+///
+/// A plain (uncompressed) file is decoded through [`grib::index::GribAccessor`],
+/// which skips reading the Section 7 payload of any message this pass would
+/// discard anyway instead of buffering it like the rest of the section; a
+/// compressed source falls back to the whole-message [`grib::from_reader`]
+/// path, since its streaming decoders aren't seekable.
+fn grib_to_messages(grib_path: &PathBuf) -> Result<Vec<Message>> {
+  let mut head = [0u8; 6];
+  let n = std::fs::File::open(grib_path)?.read(&mut head)?;
+
+  if grib::is_compressed(&head[..n]) {
+    grib_to_messages_buffered(grib_path)
+  } else {
+    grib_to_messages_indexed(grib_path)
+  }
+}
+
+fn grib_to_messages_buffered(grib_path: &PathBuf) -> Result<Vec<Message>> {
+  let file = std::fs::File::open(grib_path)?;
+  let grib = grib::from_reader(std::io::BufReader::new(file)).map_err(anyhow::Error::from)?;
+
+  let mut messages = Vec::with_capacity(grib.messages.len());
+  for message in &grib.messages {
+    let grid = match &message.grid_definition.grid {
+      Grid::Grid0(grid) => grid,
+      Grid::Unknown(_) => {
+        warn!("Skipping message with unsupported grid template {}", message.grid_definition.template_number);
+        continue;
+      }
+    };
+    let definition = &message.product_definition.product;
+    let product = match definition.base() {
+      Some(product) => product,
+      None => {
+        warn!("Skipping message with unsupported product template {}", message.product_definition.template_number);
+        continue;
+      }
+    };
+
+    // The wind grids are the deterministic, instantaneous field: skip perturbed
+    // ensemble members (keep the control run) and statistically-processed
+    // fields (averages/accumulations) so a mix of members never feeds a grid.
+    if definition.perturbation_number().map_or(false, |n| n != 0) {
+      debug!("Skipping ensemble member {}", definition.perturbation_number().unwrap());
+      continue;
+    }
+    if let Some(process) = definition.statistical_process() {
+      debug!("Skipping statistically-processed field (process {})", process);
+      continue;
+    }
+
+    let surface = &product.first_surface;
+    let surface1_value = surface.scaled_value as f64 / 10f64.powi(surface.scale_factor as i32);
+
+    let data = message.decode().map_err(anyhow::Error::from)?;
+
+    messages.push(Message {
+      header: Header {
+        discipline: message.indicator.discipline,
+        parameter_category: product.parameter_category,
+        parameter_number: product.parameter_number,
+        surface1_type: surface.surface_type,
+        surface1_value,
+        nx: grid.n_i as usize,
+        ny: grid.n_j as usize,
+        la1: grid.la1 as f64 / 1_000_000.0,
+        lo1: grid.lo1 as f64 / 1_000_000.0,
+        dx: grid.d_i as f64 / 1_000_000.0,
+        dy: grid.d_j as f64 / 1_000_000.0,
+      },
+      data,
+    });
+  }
+
+  Ok(messages)
+}
+
+fn grib_to_messages_indexed(grib_path: &PathBuf) -> Result<Vec<Message>> {
+  let file = std::fs::File::open(grib_path)?;
+  let mut accessor = grib::index::GribAccessor::open(std::io::BufReader::new(file), 8).map_err(anyhow::Error::from)?;
+
+  let mut messages = Vec::with_capacity(accessor.messages().len());
+  for position in 0..accessor.messages().len() {
+    let message = &accessor.messages()[position];
+
+    let grid = match &message.grid_definition.grid {
+      Grid::Grid0(grid) => grid.clone(),
+      Grid::Unknown(_) => {
+        warn!("Skipping message with unsupported grid template {}", message.grid_definition.template_number);
+        continue;
+      }
+    };
+    let definition = &message.product_definition.product;
+    let product = match definition.base() {
+      Some(product) => product.clone(),
+      None => {
+        warn!("Skipping message with unsupported product template {}", message.product_definition.template_number);
+        continue;
+      }
+    };
+
+    // The wind grids are the deterministic, instantaneous field: skip perturbed
+    // ensemble members (keep the control run) and statistically-processed
+    // fields (averages/accumulations) so a mix of members never feeds a grid.
+    if definition.perturbation_number().map_or(false, |n| n != 0) {
+      debug!("Skipping ensemble member {}", definition.perturbation_number().unwrap());
+      continue;
+    }
+    if let Some(process) = definition.statistical_process() {
+      debug!("Skipping statistically-processed field (process {})", process);
+      continue;
+    }
+
+    let surface = &product.first_surface;
+    let surface1_value = surface.scaled_value as f64 / 10f64.powi(surface.scale_factor as i32);
+    let discipline = message.discipline;
+
+    let data = accessor.decode(position).map_err(anyhow::Error::from)?;
+
+    messages.push(Message {
+      header: Header {
+        discipline,
+        parameter_category: product.parameter_category,
+        parameter_number: product.parameter_number,
+        surface1_type: surface.surface_type,
+        surface1_value,
+        nx: grid.n_i as usize,
+        ny: grid.n_j as usize,
+        la1: grid.la1 as f64 / 1_000_000.0,
+        lo1: grid.lo1 as f64 / 1_000_000.0,
+        dx: grid.d_i as f64 / 1_000_000.0,
+        dy: grid.d_j as f64 / 1_000_000.0,
+      },
+      data: (*data).clone(),
+    });
+  }
+
+  Ok(messages)
+}
+
 pub type Winds = Arc<RwLock<Status>>;
 
 #[async_trait]
@@ -466,6 +892,30 @@ pub trait WindsSpec {
   async fn contains_key(&self, forecast_time: &ForecastTime) -> bool;
 
   async fn find(&self, m: &DateTime<Utc>) -> (Vec<Arc<Wind>>, Option<Vec<Arc<Wind>>>, f64);
+
+  /// Interpolated `(u, v)` at an instant and position: samples the two
+  /// time-adjacent grids returned by [`find`](WindsSpec::find) with `uv_at` and
+  /// blends them with the time fraction. Falls back to a single grid (or
+  /// `(0, 0)` when no forecast is loaded).
+  async fn wind_at(&self, m: &DateTime<Utc>, lat: f64, lon: f64) -> (f64, f64);
+
+  async fn set_paused(&self, paused: bool);
+
+  async fn cancel(&self);
+
+  async fn reset_cancel(&self);
+
+  async fn is_cancelled(&self) -> bool;
+
+  /// Block while the scheduler is paused; returns `false` as soon as the job is
+  /// cancelled so the caller can bail out of its task loop.
+  async fn wait_while_paused(&self) -> bool;
+
+  async fn start_job(&self, report: JobReport);
+
+  async fn mark_task(&self, forecast_hour: u16, state: TaskState);
+
+  async fn report(&self) -> Option<JobReport>;
 }
 
 #[async_trait]
@@ -477,6 +927,7 @@ impl WindsSpec for Winds {
       ref_time: l.ref_time,
       forecast_time: l.forecast_time,
       wind: None,
+      hash: l.hash.clone(),
     })
   }
 
@@ -511,7 +962,18 @@ impl WindsSpec for Winds {
   async fn set_last(&self, ref_time: DateTime<Utc>, forecast_time: u16, max_forecast_time: u16) {
     let mut it = self.write().await;
 
-    if it.last.is_none() || it.last.as_ref().unwrap().ref_time <= ref_time {
+    // Only ever advance `last`: with concurrent downloads a lower forecast hour
+    // can finish after a higher one, and under the same ref-time that must not
+    // drag the reported progress back down.
+    let advance = match &it.last {
+      None => true,
+      Some(last) => {
+        last.ref_time < ref_time
+          || (last.ref_time == ref_time && last.forecast_hour() < forecast_time)
+      }
+    };
+
+    if advance {
       it.last = Some((&ref_time, forecast_time).into());
       it.progress = (100 * forecast_time / max_forecast_time) as u8;
     }
@@ -569,6 +1031,69 @@ impl WindsSpec for Winds {
 
     (w1, None, 0.0)
   }
+
+  async fn wind_at(&self, m: &DateTime<Utc>, lat: f64, lon: f64) -> (f64, f64) {
+    let (w1, w2, fraction) = self.find(m).await;
+
+    let a = match w1.first() {
+      Some(wind) => wind.uv_at(lat, lon),
+      None => return (0.0, 0.0),
+    };
+
+    match w2.as_ref().and_then(|w| w.first()) {
+      Some(wind) => {
+        let b = wind.uv_at(lat, lon);
+        (a.0 + (b.0 - a.0) * fraction, a.1 + (b.1 - a.1) * fraction)
+      },
+      None => a,
+    }
+  }
+
+  async fn set_paused(&self, paused: bool) {
+    self.write().await.paused = paused;
+  }
+
+  async fn cancel(&self) {
+    self.write().await.cancelled = true;
+  }
+
+  async fn reset_cancel(&self) {
+    self.write().await.cancelled = false;
+  }
+
+  async fn is_cancelled(&self) -> bool {
+    self.read().await.cancelled
+  }
+
+  async fn wait_while_paused(&self) -> bool {
+    loop {
+      {
+        let it = self.read().await;
+        if it.cancelled {
+          return false;
+        }
+        if !it.paused {
+          return true;
+        }
+      }
+      time::sleep(time::Duration::from_secs(1)).await;
+    }
+  }
+
+  async fn start_job(&self, report: JobReport) {
+    self.write().await.job = Some(report);
+  }
+
+  async fn mark_task(&self, forecast_hour: u16, state: TaskState) {
+    let mut it = self.write().await;
+    if let Some(job) = it.job.as_mut() {
+      job.mark(forecast_hour, state);
+    }
+  }
+
+  async fn report(&self) -> Option<JobReport> {
+    self.read().await.job.clone()
+  }
 }
 
 pub struct Status {
@@ -578,6 +1103,12 @@ pub struct Status {
   pub(crate) last: Option<Stamp>,
   pub(crate) progress: u8,
   pub forecasts: BTreeMap<ForecastTime, Vec<Stamp>>,
+  /// Download scheduler state: when `paused` is set the scheduler stops picking
+  /// up new tasks, `cancelled` aborts the in-flight job, and `job` mirrors the
+  /// per-stamp task states for the structured progress report.
+  pub(crate) paused: bool,
+  pub(crate) cancelled: bool,
+  pub(crate) job: Option<JobReport>,
 }
 
 impl Display for Status {
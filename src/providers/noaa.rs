@@ -1,21 +1,26 @@
 use async_recursion::async_recursion;
+use futures::stream::StreamExt;
 use std::path::PathBuf;
 use std::fs;
 use std::io::Write;
 use std::ops::Neg;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use chrono::{DateTime, Duration, Utc};
 use http::StatusCode;
-use tempfile::NamedTempFile;
 use tokio::sync::{RwLock};
 use crate::config::{NoaaProviderConfig, Storage};
 use crate::providers::{Provider, Status, WindsSpec, Winds};
+use crate::providers::job::{JobReport, TaskState};
+use crate::providers::retry::{self, RetryPolicy};
 use crate::error::{Error, Result};
-use crate::stamp::{Durations, ForecastTime, ForecastTimeSpec, RefTime, RefTimeSpec, Stamp};
+use crate::stamp::{Durations, RefTime, RefTimeSpec, Stamp};
 
 pub struct Noaa {
     pub(crate) status: Winds,
     jsons: Storage,
+    native: bool,
+    retry: RetryPolicy,
 }
 
 impl Noaa {
@@ -40,16 +45,21 @@ impl Noaa {
                 current_ref_time: Self::current_ref_time(),
                 last: None,
                 progress: 0,
-                forecasts: Default::default()
+                forecasts: Default::default(),
+                paused: false,
+                cancelled: false,
+                job: None,
             })),
-            jsons: Storage::Local { dir: jsons_dir },
+            jsons: Storage::Local { dir: jsons_dir, compression: None, split: None },
+            native: false,
+            retry: RetryPolicy::default(),
         })
 
     }
 
     pub(crate) fn from_config(config: &NoaaProviderConfig) -> Result<Self> {
         match &config.jsons {
-            Storage::Local{dir} => Self::create_dir(&dir.into()),
+            Storage::Local { dir, .. } => Self::create_dir(&dir.into()),
             _ => {}
         }
 
@@ -60,9 +70,14 @@ impl Noaa {
                 current_ref_time: Self::current_ref_time(),
                 last: None,
                 progress: 0,
-                forecasts: Default::default()
+                forecasts: Default::default(),
+                paused: false,
+                cancelled: false,
+                job: None,
             })),
             jsons: config.jsons.clone(),
+            native: config.native,
+            retry: RetryPolicy::from(&config.retry),
         })
     }
 
@@ -88,46 +103,82 @@ impl Noaa {
     #[async_recursion]
     async fn download_next(&self, first: bool, ref_time: RefTime) -> Result<bool> {
 
+        // Probe the first forecast hour: a 404 here means this run is not
+        // published yet, so when scanning the latest run we step back one cycle.
+        let first_hour = 6;
+        let probe: Stamp = (&ref_time, first_hour).into();
         let mut something_new = false;
 
-        let mut h = 6;
-        let mut first = first;
+        if !self.jsons.exists(probe.file_name()).await? {
+            match retry::retry(&self.retry, &probe.to_string(), || self.download_grib(&probe)).await {
+                Ok(()) => {
+                    something_new = true;
+                    self.on_stamp_downloaded(true, false, probe).await;
+                },
+                Err(Error::StampNotFoundError()) => {
+                    if first {
+                        return self.download_next(false, (ref_time - 6.hours()).into()).await;
+                    }
+                    return Ok(false);
+                },
+                Err(e) => {
+                    error!("Error downloading grib `{}` : {:?}", probe, e);
+                    return Err(e);
+                }
+            }
+        }
 
+        // Re-derive the remaining tasks from what is already in storage so a run
+        // interrupted by a crash or a `cancel()` resumes from where it left off
+        // instead of restarting the whole 384-hour sweep.
+        let mut hours = Vec::new();
+        let mut h = first_hour + self.step();
         while h <= self.max_forecast_hour() {
-            let forecast_time = ForecastTime::from_ref_time(&ref_time, h);
-
-            if forecast_time.from_now() <= self.step().hours().neg() {
-                h += self.step();
-                continue;
+            let stamp: Stamp = (&ref_time, h).into();
+            if stamp.from_now() > self.step().hours().neg() && !self.jsons.exists(stamp.file_name()).await? {
+                hours.push(h);
             }
+            h += self.step();
+        }
 
-            let stamp: Stamp = (&ref_time, forecast_time).into();
-
-            if !self.jsons.exists(stamp.file_name()).await? {
+        self.status().reset_cancel().await;
+        self.status().start_job(JobReport::new(ref_time, hours.iter().copied())).await;
+
+        // Download the remaining stamps with bounded concurrency, honouring
+        // pause/cancel between tasks.
+        let something_new = AtomicBool::new(something_new);
+        let concurrency = self.concurrency();
+        futures::stream::iter(hours.into_iter())
+            .for_each_concurrent(concurrency, |h| {
+                let something_new = &something_new;
+                async move {
+                    if !self.status().wait_while_paused().await {
+                        return;
+                    }
 
-                match self.download_grib(&stamp).await {
-                    Ok(()) => {
-                        something_new = true;
-                        self.on_stamp_downloaded(true, false, stamp).await;
-                    },
-                    Err(Error::StampNotFoundError()) => {
-                        if first {
-                            return self.download_next(false, (ref_time - 6.hours()).into()).await;
+                    let stamp: Stamp = (&ref_time, h).into();
+                    self.status().mark_task(h, TaskState::Running).await;
+
+                    match retry::retry(&self.retry, &stamp.to_string(), || self.download_grib(&stamp)).await {
+                        Ok(()) => {
+                            something_new.store(true, Ordering::Relaxed);
+                            self.status().mark_task(h, TaskState::Done).await;
+                            self.on_stamp_downloaded(true, false, stamp).await;
+                        },
+                        Err(Error::StampNotFoundError()) => {
+                            // Forecast hour not published yet; leave it pending
+                            // so the next cycle retries it.
+                            self.status().mark_task(h, TaskState::Pending).await;
+                        },
+                        Err(e) => {
+                            error!("Error downloading grib `{}` : {:?}", stamp, e);
+                            self.status().mark_task(h, TaskState::Failed).await;
                         }
-                        break;
-                    }
-                    Err(e) => {
-                        error!("Error downloading grib `{}` : {:?}", stamp, e);
-                        break;
                     }
                 }
-            }
+            }).await;
 
-            h += self.step();
-            first = false;
-        }
-
-        Ok(something_new)
+        Ok(something_new.load(Ordering::Relaxed))
     }
 
     async fn download_grib(&self, stamp: &Stamp) -> Result<()> {
@@ -138,7 +189,15 @@ impl Noaa {
             .timeout(std::time::Duration::from_secs(30))
             .build()
             .unwrap();
-        let req = client.get(url).query(&[
+
+        // Bytes already on disk from an interrupted attempt are kept in a
+        // sidecar `.part` file so a retry resumes via an HTTP `Range` request
+        // instead of re-fetching the whole (multi-hundred-MB) GFS file from
+        // scratch.
+        let part_path = std::env::temp_dir().join(format!("{}.part", stamp.file_name()));
+        let resume_from = std::fs::metadata(&part_path).map(|m| m.len()).unwrap_or(0);
+
+        let mut req = client.get(url).query(&[
             ("dir", format!("/gfs.{}/{}/atmos", stamp.ref_time.format("%Y%m%d"), stamp.ref_time.format("%H")).as_str()),
             ("file", format!("gfs.t{}z.pgrb2.1p00.f{:03}", stamp.ref_time.format("%H"), stamp.forecast_hour()).as_str()),
             ("lev_10_m_above_ground", "on"),
@@ -148,40 +207,53 @@ impl Noaa {
             ("rightlon", "360"),
             ("toplat", "90"),
             ("bottomlat", "-90"),
-        ]).build()?;
+        ]);
+        if resume_from > 0 {
+            req = req.header(reqwest::header::RANGE, format!("bytes={}-", resume_from));
+        }
+        let req = req.build()?;
 
-        debug!("`{}` Try to download {}", stamp, req.url());
+        debug!("`{}` Try to download {} (from byte {})", stamp, req.url(), resume_from);
 
         match client.execute(req).await {
             Ok(response) => {
-                match response.status() {
-                    StatusCode::OK => {
-                        let file = NamedTempFile::new()?;
-
-                        let (mut file, path) = file.into_parts();
-                        file.write(response.bytes().await?.as_ref())?;
-
-                        match self.on_file_downloaded(path.to_path_buf(), stamp).await {
-                            Ok(()) => {
-                                std::fs::remove_file(path).unwrap_or_default();
-
-                                info!("`{}` Downloaded", stamp);
-
-                                Ok(())
-                            }
-                            Err(e) => {
-                                std::fs::remove_file(path)?;
-                                Err(e)
-                            }
-                        }
-                    },
+                // Append when the server honoured the range, otherwise restart
+                // the file from scratch (a plain 200 means it ignored the
+                // `Range` header).
+                let mut file = match response.status() {
+                    StatusCode::OK => std::fs::File::create(&part_path)?,
+                    StatusCode::PARTIAL_CONTENT => std::fs::OpenOptions::new().append(true).create(true).open(&part_path)?,
                     StatusCode::NOT_FOUND => {
                         debug!("Download failed `{}` : {}", stamp, StatusCode::NOT_FOUND);
-                        Err(Error::StampNotFoundError())
+                        return Err(Error::StampNotFoundError());
                     },
                     any => {
                         warn!("Download failed `{}` : {}", stamp, any);
-                        Err(Error::Error())
+                        return Err(Error::Error());
+                    }
+                };
+
+                // Stream the body chunk-by-chunk so memory stays bounded
+                // regardless of forecast-file size instead of buffering
+                // the whole GRIB with `response.bytes()`.
+                let mut stream = response.bytes_stream();
+                while let Some(chunk) = stream.next().await {
+                    file.write_all(chunk?.as_ref())?;
+                }
+                file.flush()?;
+                drop(file);
+
+                match self.on_file_downloaded(part_path.clone(), stamp).await {
+                    Ok(()) => {
+                        std::fs::remove_file(&part_path).unwrap_or_default();
+
+                        info!("`{}` Downloaded", stamp);
+
+                        Ok(())
+                    }
+                    Err(e) => {
+                        std::fs::remove_file(&part_path)?;
+                        Err(e)
                     }
                 }
             },
@@ -200,6 +272,10 @@ impl Provider for Noaa {
         String::from("noaa")
     }
 
+    fn native(&self) -> bool {
+        self.native
+    }
+
     fn jsons_storage(&self) -> Storage {
         self.jsons.clone()
     }
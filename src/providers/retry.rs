@@ -0,0 +1,79 @@
+use std::future::Future;
+use std::time::{Duration, SystemTime, UNIX_EPOCH};
+use crate::config::RetryConfig;
+use crate::error::{Error, Result};
+
+/// Exponential-backoff policy for the fetch-and-convert step. A single transient
+/// HTTP hiccup or a truncated GRIB no longer drops a forecast hour until the
+/// next cycle: the operation is retried with growing, jittered delays.
+#[derive(Clone, Debug)]
+pub(crate) struct RetryPolicy {
+  max_attempts: u32,
+  base_delay: Duration,
+  max_delay: Duration,
+}
+
+impl Default for RetryPolicy {
+  fn default() -> Self {
+    Self {
+      max_attempts: 5,
+      base_delay: Duration::from_secs(1),
+      max_delay: Duration::from_secs(60),
+    }
+  }
+}
+
+impl From<&RetryConfig> for RetryPolicy {
+  fn from(config: &RetryConfig) -> Self {
+    Self {
+      max_attempts: config.max_attempts.max(1),
+      base_delay: Duration::from_secs(config.base_delay_secs),
+      max_delay: Duration::from_secs(config.max_delay_secs),
+    }
+  }
+}
+
+impl RetryPolicy {
+  /// Delay before the given zero-based attempt: `base * 2^attempt`, capped at
+  /// `max_delay`, plus up to 25% jitter so concurrent retries don't stampede
+  /// the upstream in lockstep.
+  fn backoff(&self, attempt: u32) -> Duration {
+    let factor = 1u32.checked_shl(attempt.min(16)).unwrap_or(u32::MAX);
+    let capped = self.base_delay.saturating_mul(factor).min(self.max_delay);
+    capped + capped.mul_f64(0.25 * jitter())
+  }
+}
+
+/// Pseudo-random fraction in `[0, 1)` seeded from the wall clock; good enough to
+/// decorrelate backoff across tasks without pulling in an rng dependency.
+fn jitter() -> f64 {
+  let nanos = SystemTime::now().duration_since(UNIX_EPOCH).map(|d| d.subsec_nanos()).unwrap_or(0);
+  (nanos % 1_000) as f64 / 1_000.0
+}
+
+/// Run `op` under `policy`, retrying transient failures. `StampNotFoundError`
+/// (HTTP 404 — the forecast isn't published yet) is a soft skip and returned
+/// immediately without counting as a failure.
+pub(crate) async fn retry<F, Fut, T>(policy: &RetryPolicy, label: &str, mut op: F) -> Result<T>
+where
+  F: FnMut() -> Fut,
+  Fut: Future<Output = Result<T>>,
+{
+  let mut attempt = 0;
+  loop {
+    match op().await {
+      Ok(value) => return Ok(value),
+      Err(Error::StampNotFoundError()) => return Err(Error::StampNotFoundError()),
+      Err(e) => {
+        attempt += 1;
+        if attempt >= policy.max_attempts {
+          error!("{} - giving up after {} attempt(s) : {:?}", label, attempt, e);
+          return Err(e);
+        }
+        let delay = policy.backoff(attempt - 1);
+        warn!("{} - attempt {}/{} failed ({:?}), retrying in {:?}", label, attempt, policy.max_attempts, e, delay);
+        tokio::time::sleep(delay).await;
+      }
+    }
+  }
+}
@@ -1,23 +1,37 @@
 use async_recursion::async_recursion;
+use futures::stream::StreamExt;
 use std::path::PathBuf;
 use std::fs;
 use std::io::Write;
 use std::ops::Neg;
 use std::sync::Arc;
+use std::sync::atomic::{AtomicBool, Ordering};
 use chrono::{DateTime, Duration, Timelike, Utc};
 use http::StatusCode;
 use image::GenericImageView;
 use image::io::Reader as ImageReader;
-use tempfile::NamedTempFile;
 use tokio::sync::{RwLock};
 use crate::config::{Storage, ZezoProviderConfig};
 use crate::providers::{Provider, Status, WindsSpec, Winds, Wind};
+use crate::providers::job::{JobReport, TaskState};
+use crate::providers::retry::{self, RetryPolicy};
 use crate::error::{Error, Result};
 use crate::stamp::{Durations, ForecastTime, ForecastTimeSpec, RefTime, RefTimeSpec, Stamp};
 
 pub struct Zezo {
     pub(crate) status: Winds,
     pngs: Storage,
+    retry: RetryPolicy,
+    client: reqwest::Client,
+}
+
+/// Build the single `reqwest::Client` reused for every download so connections
+/// and TLS sessions are pooled across the hundreds of stamps in a refresh.
+fn build_client() -> reqwest::Client {
+    reqwest::Client::builder()
+        .timeout(std::time::Duration::from_secs(30))
+        .build()
+        .unwrap()
 }
 
 impl Zezo {
@@ -42,16 +56,21 @@ impl Zezo {
                 current_ref_time: Self::current_ref_time(),
                 last: None,
                 progress: 0,
-                forecasts: Default::default()
+                forecasts: Default::default(),
+                paused: false,
+                cancelled: false,
+                job: None,
             })),
-            pngs: Storage::Local { dir: pngs_dir },
+            pngs: Storage::Local { dir: pngs_dir, compression: None, split: None },
+            retry: RetryPolicy::default(),
+            client: build_client(),
         })
 
     }
 
     pub(crate) fn from_config(config: &ZezoProviderConfig) -> Result<Self> {
         match &config.pngs {
-            Storage::Local{dir} => Self::create_dir(&dir.into()),
+            Storage::Local { dir, .. } => Self::create_dir(&dir.into()),
             _ => {}
         }
 
@@ -62,9 +81,14 @@ impl Zezo {
                 current_ref_time: Self::current_ref_time(),
                 last: None,
                 progress: 0,
-                forecasts: Default::default()
+                forecasts: Default::default(),
+                paused: false,
+                cancelled: false,
+                job: None,
             })),
             pngs: config.pngs.clone(),
+            retry: RetryPolicy::from(&config.retry),
+            client: build_client(),
         })
     }
 
@@ -90,96 +114,150 @@ impl Zezo {
     #[async_recursion]
     async fn download_next(&self, first: bool, ref_time: RefTime) -> Result<bool> {
 
+        // Probe the first forecast hour: a 404 here means this run is not
+        // published yet, so when scanning the latest run we step back one cycle.
+        let first_hour = 6;
+        let probe: Stamp = (&ref_time, ForecastTime::from_ref_time(&ref_time, first_hour)).into();
         let mut something_new = false;
 
-        let mut h = 6;
-        let mut first = first;
+        if !self.pngs.exists(probe.file_name()).await? {
+            match retry::retry(&self.retry, &probe.to_string(), || self.download_png(&probe)).await {
+                Ok(()) => {
+                    something_new = true;
+                    self.on_stamp_downloaded(true, false, probe).await;
+                },
+                Err(Error::StampNotFoundError()) => {
+                    if first {
+                        return self.download_next(false, (ref_time - 6.hours()).into()).await;
+                    }
+                    return Ok(false);
+                },
+                Err(e) => {
+                    error!("Error downloading png `{}` : {:?}", probe, e);
+                    return Err(e);
+                }
+            }
+        }
 
+        // Re-derive the remaining tasks from what is already in storage so a run
+        // interrupted by a crash or a `cancel()` resumes from where it left off
+        // instead of restarting the whole 384-hour sweep.
+        let mut hours = Vec::new();
+        let mut h = first_hour + self.step();
         while h <= self.max_forecast_hour() {
             let forecast_time = ForecastTime::from_ref_time(&ref_time, h);
-
-            if forecast_time.from_now() <= self.step().hours().neg() {
-                h += self.step();
-                continue;
-            }
-
             let stamp: Stamp = (&ref_time, forecast_time).into();
+            if forecast_time.from_now() > self.step().hours().neg() && !self.pngs.exists(stamp.file_name()).await? {
+                hours.push(h);
+            }
+            h += self.step();
+        }
 
-            if !self.pngs.exists(stamp.file_name()).await? {
+        self.status().reset_cancel().await;
+        self.status().start_job(JobReport::new(ref_time, hours.iter().copied())).await;
+
+        // Download the remaining stamps with bounded concurrency, honouring
+        // pause/cancel between tasks.
+        let something_new = AtomicBool::new(something_new);
+        let concurrency = self.concurrency();
+        futures::stream::iter(hours.into_iter())
+            .for_each_concurrent(concurrency, |h| {
+                let something_new = &something_new;
+                async move {
+                    if !self.status().wait_while_paused().await {
+                        return;
+                    }
 
-                match self.download_png(&stamp).await {
-                    Ok(()) => {
-                        something_new = true;
-                        self.on_stamp_downloaded(true, false, stamp).await;
-                    },
-                    Err(Error::StampNotFoundError()) => {
-                        if first {
-                            return self.download_next(false, (ref_time - 6.hours()).into()).await;
+                    let stamp: Stamp = (&ref_time, ForecastTime::from_ref_time(&ref_time, h)).into();
+                    self.status().mark_task(h, TaskState::Running).await;
+
+                    match retry::retry(&self.retry, &stamp.to_string(), || self.download_png(&stamp)).await {
+                        Ok(()) => {
+                            something_new.store(true, Ordering::Relaxed);
+                            self.status().mark_task(h, TaskState::Done).await;
+                            self.on_stamp_downloaded(true, false, stamp).await;
+                        },
+                        Err(Error::StampNotFoundError()) => {
+                            // Forecast hour not published yet; leave it pending
+                            // so the next cycle retries it.
+                            self.status().mark_task(h, TaskState::Pending).await;
+                        },
+                        Err(e) => {
+                            error!("Error downloading png `{}` : {:?}", stamp, e);
+                            self.status().mark_task(h, TaskState::Failed).await;
                         }
-                        break;
-                    }
-                    Err(e) => {
-                        error!("Error downloading png `{}` : {:?}", stamp, e);
-                        break;
                     }
                 }
-            }
-
-            h += self.step();
-            first = false;
-        }
+            }).await;
 
-        Ok(something_new)
+        Ok(something_new.load(Ordering::Relaxed))
     }
 
     async fn download_png(&self, stamp: &Stamp) -> Result<()> {
 
         let url = format!("http://fr.zezo.org/windp/{}_{:03}_{}.png", stamp.forecast_time.format("%Y%m%d"), stamp.forecast_time.hour(), stamp.ref_time.hour());
 
-        let client = reqwest::Client::builder()
-            .timeout(std::time::Duration::from_secs(30))
-            .build()
-            .unwrap();
-        let req = client.get(url).build()?;
+        // Bytes already on disk from an interrupted attempt are kept in a
+        // sidecar `.part` file so a retry resumes via an HTTP `Range` request
+        // instead of re-fetching the whole image.
+        let part_path = std::env::temp_dir().join(format!("{}.part", stamp.file_name()));
+        let resume_from = std::fs::metadata(&part_path).map(|m| m.len()).unwrap_or(0);
 
-        debug!("`{}` Try to download {}", stamp, req.url());
+        let mut req = self.client.get(&url);
+        if resume_from > 0 {
+            req = req.header(reqwest::header::RANGE, format!("bytes={}-", resume_from));
+        }
+        let req = req.build()?;
 
-        match client.execute(req).await {
-            Ok(response) => {
-                match response.status() {
-                    StatusCode::OK => {
-                        let file = NamedTempFile::new()?;
+        debug!("`{}` Try to download {} (from byte {})", stamp, req.url(), resume_from);
 
-                        let (mut file, path) = file.into_parts();
-                        file.write(response.bytes().await?.as_ref())?;
+        let response = match self.client.execute(req).await {
+            Ok(response) => response,
+            Err(e) => {
+                error!("Error downloading png file {} : {}", stamp, e);
+                return Err(Error::Error());
+            }
+        };
+
+        // Append when the server honoured the range, otherwise restart the file
+        // from scratch (a plain 200 means it ignored the `Range` header).
+        let mut file = match response.status() {
+            StatusCode::OK => std::fs::File::create(&part_path)?,
+            StatusCode::PARTIAL_CONTENT => std::fs::OpenOptions::new().append(true).create(true).open(&part_path)?,
+            StatusCode::NOT_FOUND => {
+                debug!("Download failed `{}` : {}", stamp, StatusCode::NOT_FOUND);
+                return Err(Error::StampNotFoundError());
+            },
+            any => {
+                warn!("Download failed `{}` : {}", stamp, any);
+                return Err(Error::Error());
+            }
+        };
+
+        // Stream the body chunk-by-chunk so memory stays bounded regardless of
+        // image size instead of buffering the whole response.
+        let mut stream = response.bytes_stream();
+        while let Some(chunk) = stream.next().await {
+            let chunk = chunk.map_err(|e| {
+                error!("Error streaming png file {} : {}", stamp, e);
+                Error::Error()
+            })?;
+            file.write_all(chunk.as_ref())?;
+        }
+        file.flush()?;
+        drop(file);
 
-                        match self.on_file_downloaded(path.to_path_buf(), stamp).await {
-                            Ok(()) => {
-                                std::fs::remove_file(path).unwrap_or_default();
+        match self.on_file_downloaded(part_path.clone(), stamp).await {
+            Ok(()) => {
+                std::fs::remove_file(&part_path).unwrap_or_default();
 
-                                info!("`{}` Downloaded", stamp);
+                info!("`{}` Downloaded", stamp);
 
-                                Ok(())
-                            }
-                            Err(e) => {
-                                std::fs::remove_file(path)?;
-                                Err(e)
-                            }
-                        }
-                    },
-                    StatusCode::NOT_FOUND => {
-                        debug!("Download failed `{}` : {}", stamp, StatusCode::NOT_FOUND);
-                        Err(Error::StampNotFoundError())
-                    },
-                    any => {
-                        warn!("Download failed `{}` : {}", stamp, any);
-                        Err(Error::Error())
-                    }
-                }
-            },
+                Ok(())
+            }
             Err(e) => {
-                error!("Error downloading png file {} : {}", stamp, e);
-                Err(Error::Error())
+                std::fs::remove_file(&part_path)?;
+                Err(e)
             }
         }
     }
@@ -250,7 +328,10 @@ impl Provider for Zezo {
     }
 
     async fn on_file_downloaded(&self, file: PathBuf, stamp: &Stamp) -> Result<()> {
-        self.pngs.save(&file, stamp.file_name()).await?;
+        let (changed, hash) = self.pngs.save_if_changed(&file, stamp.file_name()).await?;
+        if !changed {
+            debug!("{} - `{}` unchanged, kept existing data ({}...)", self.id(), stamp, &hash[..12.min(hash.len())]);
+        }
 
         std::fs::remove_file(&file).unwrap_or_default();
 
@@ -289,7 +370,10 @@ impl Provider for Zezo {
             delta_lat: 1.0,
             delta_lon: 1.0,
             n_lat: 180,
-            n_lon: 361,
+            // 360 distinct longitude samples; `raw_u`/`raw_v` are already
+            // 361 long (the wrap column appended above), matching the
+            // `n_lon + 1` row-length invariant `Wind::sample` relies on.
+            n_lon: 360,
             u: u.into_boxed_slice(),
             v: v.into_boxed_slice()
         })
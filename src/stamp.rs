@@ -49,6 +49,10 @@ pub struct Stamp {
     pub ref_time: RefTime,
     pub forecast_time: ForecastTime,
     pub(crate) wind: Option<Arc<Wind>>,
+    /// Content hash of the decoded forecast data, set once the stamp has been
+    /// written through `Storage`; lets callers tell when a stamp's data actually
+    /// changed versus merely being re-downloaded.
+    pub(crate) hash: Option<String>,
 }
 
 impl Stamp {
@@ -84,7 +88,8 @@ impl TryFrom<&String> for Stamp {
                 let res = Self {
                     ref_time,
                     forecast_time: ref_time + forecast_hour.hours(),
-                    wind: None
+                    wind: None,
+                    hash: None
                 };
 
                 Ok(res)
@@ -115,6 +120,7 @@ impl From<(&RefTime, ForecastTime)> for Stamp {
             ref_time: ref_time.clone(),
             forecast_time,
             wind: None,
+            hash: None,
         }
     }
 }
@@ -125,6 +131,7 @@ impl From<(&RefTime, u16)> for Stamp {
             ref_time: ref_time.clone(),
             forecast_time: *ref_time + Duration::hours(h as i64),
             wind: None,
+            hash: None,
         }
     }
 }
@@ -0,0 +1,195 @@
+use std::path::Path;
+use std::sync::OnceLock;
+
+use anyhow::anyhow;
+
+use crate::stamp::{RefTime, Stamp};
+
+/// Process-wide warm cache, set once at start-up from the configured store path.
+/// The download and read paths consult it through [`global`] so grids decoded in
+/// a previous run survive a restart; when no store is configured it stays empty
+/// and every lookup misses harmlessly.
+static GLOBAL: OnceLock<Store> = OnceLock::new();
+
+/// Install the process-wide [`Store`]. A second call is ignored, keeping the
+/// first configured store authoritative for the lifetime of the process.
+pub(crate) fn init_global(store: Store) {
+  let _ = GLOBAL.set(store);
+}
+
+/// The process-wide [`Store`], or `None` when persistence is disabled.
+pub(crate) fn global() -> Option<&'static Store> {
+  GLOBAL.get()
+}
+
+/// Persistent, embedded key-value cache of decoded wind grids, keyed by
+/// [`Stamp`] and backed by [`sled`]. A process keeps its warm grids here so a
+/// restart reloads them straight from disk instead of re-downloading and
+/// re-decoding every forecast.
+///
+/// The stored value is the compact binary cache produced by
+/// [`Wind::to_cache_bytes`](crate::providers::Wind::to_cache_bytes); the read
+/// path reconstructs the grid with the matching
+/// [`Wind::from_cache_bytes`](crate::providers::Wind::from_cache_bytes).
+///
+/// `sled::Db` is internally reference-counted, so cloning a `Store` is cheap and
+/// every clone points at the same tree.
+#[derive(Clone)]
+pub(crate) struct Store {
+  db: sled::Db,
+}
+
+/// Magic prefix written at the head of a [`Store::backup`] archive so a stray
+/// file is rejected rather than imported as garbage.
+const BACKUP_MAGIC: &[u8] = b"WSTOREBK1";
+
+impl Store {
+  /// Open (creating if necessary) the store rooted at `path`.
+  pub(crate) fn open<P: AsRef<Path>>(path: P) -> anyhow::Result<Store> {
+    let db = sled::open(path)?;
+    Ok(Store { db })
+  }
+
+  /// Encode a stamp as a key within `namespace`: the namespace (the provider's
+  /// `Storage` identity, so grids from different backends never collide),
+  /// a `0x00` separator, then the reference and forecast times as big-endian
+  /// second counts. Big-endian ordering keeps a namespace's ref-time forecasts
+  /// contiguous and chronologically sorted, which is what [`range`](Self::range)
+  /// and [`evict_expired`](Self::evict_expired) rely on.
+  fn key(namespace: &str, stamp: &Stamp) -> Vec<u8> {
+    let mut key = Vec::with_capacity(namespace.len() + 1 + 16);
+    key.extend_from_slice(namespace.as_bytes());
+    key.push(0);
+    key.extend_from_slice(&stamp.ref_time.timestamp().to_be_bytes());
+    key.extend_from_slice(&stamp.forecast_time.timestamp().to_be_bytes());
+    key
+  }
+
+  /// Rebuild the (data-less) stamp from the trailing 16 bytes of a key.
+  fn stamp_from_key(key: &[u8]) -> anyhow::Result<Stamp> {
+    if key.len() < 16 {
+      return Err(anyhow!("malformed store key"));
+    }
+    let tail = &key[key.len() - 16..];
+    let ref_secs = i64::from_be_bytes(tail[0..8].try_into().unwrap());
+    let forecast_secs = i64::from_be_bytes(tail[8..16].try_into().unwrap());
+    Ok(Stamp {
+      ref_time: timestamp(ref_secs)?,
+      forecast_time: timestamp(forecast_secs)?,
+      wind: None,
+      hash: None,
+    })
+  }
+
+  /// Store the decoded grid bytes for `stamp` under `namespace`, overwriting any
+  /// previous value.
+  pub(crate) fn put(&self, namespace: &str, stamp: &Stamp, bytes: &[u8]) -> anyhow::Result<()> {
+    self.db.insert(Self::key(namespace, stamp), bytes)?;
+    Ok(())
+  }
+
+  /// Fetch the decoded grid bytes previously stored for `stamp` under
+  /// `namespace`, or `None` when the stamp has never been cached.
+  pub(crate) fn get(&self, namespace: &str, stamp: &Stamp) -> anyhow::Result<Option<Vec<u8>>> {
+    Ok(self.db.get(Self::key(namespace, stamp))?.map(|v| v.to_vec()))
+  }
+
+  /// Every cached stamp sharing `ref_time` within `namespace`, in ascending
+  /// forecast-time order.
+  pub(crate) fn range(&self, namespace: &str, ref_time: &RefTime) -> anyhow::Result<Vec<Stamp>> {
+    let mut prefix = Vec::with_capacity(namespace.len() + 1 + 8);
+    prefix.extend_from_slice(namespace.as_bytes());
+    prefix.push(0);
+    prefix.extend_from_slice(&ref_time.timestamp().to_be_bytes());
+    let mut stamps = Vec::new();
+    for entry in self.db.scan_prefix(prefix) {
+      let (key, _) = entry?;
+      stamps.push(Self::stamp_from_key(&key)?);
+    }
+    Ok(stamps)
+  }
+
+  /// Drop every cached stamp whose forecast time is already in the past by more
+  /// than `grace`, returning how many were evicted. This is the `drain_filter`
+  /// equivalent the in-memory [`Status`](crate::providers::Status) uses, applied
+  /// to the on-disk tree so a long-lived cache does not grow without bound.
+  pub(crate) fn evict_expired(&self, now: RefTime, grace: chrono::Duration) -> anyhow::Result<usize> {
+    let cutoff = now - grace;
+    let mut expired = Vec::new();
+    for entry in self.db.iter() {
+      let (key, _) = entry?;
+      let stamp = Self::stamp_from_key(&key)?;
+      if stamp.forecast_time < cutoff {
+        expired.push(key);
+      }
+    }
+    for key in &expired {
+      self.db.remove(key)?;
+    }
+    Ok(expired.len())
+  }
+
+  /// Dump the whole store into a single length-framed archive at `path`, so an
+  /// operator can snapshot or migrate a running cache with one file.
+  pub(crate) fn backup<P: AsRef<Path>>(&self, path: P) -> anyhow::Result<usize> {
+    let mut buf = Vec::new();
+    buf.extend_from_slice(BACKUP_MAGIC);
+
+    let mut count = 0;
+    for entry in self.db.iter() {
+      let (key, value) = entry?;
+      buf.extend_from_slice(&(key.len() as u16).to_le_bytes());
+      buf.extend_from_slice(&key);
+      buf.extend_from_slice(&(value.len() as u64).to_le_bytes());
+      buf.extend_from_slice(&value);
+      count += 1;
+    }
+
+    std::fs::write(path, buf)?;
+    Ok(count)
+  }
+
+  /// Reload an archive written by [`backup`](Self::backup) into the store,
+  /// overwriting any colliding keys. Returns the number of entries restored.
+  pub(crate) fn restore<P: AsRef<Path>>(&self, path: P) -> anyhow::Result<usize> {
+    let bytes = std::fs::read(path)?;
+    if bytes.len() < BACKUP_MAGIC.len() || &bytes[0..BACKUP_MAGIC.len()] != BACKUP_MAGIC {
+      return Err(anyhow!("not a wind store backup"));
+    }
+
+    // Read a length-prefixed field, bounds-checking every slice so a truncated
+    // or corrupt archive is reported rather than panicking on an out-of-range
+    // index.
+    let take = |bytes: &[u8], pos: &mut usize, len: usize| -> anyhow::Result<std::ops::Range<usize>> {
+      let end = pos.checked_add(len).filter(|end| *end <= bytes.len())
+        .ok_or_else(|| anyhow!("truncated wind store backup"))?;
+      let range = *pos..end;
+      *pos = end;
+      Ok(range)
+    };
+
+    let mut pos = BACKUP_MAGIC.len();
+    let mut count = 0;
+    while pos < bytes.len() {
+      let key_len = u16::from_le_bytes(bytes[take(&bytes, &mut pos, 2)?].try_into()?) as usize;
+      let key = bytes[take(&bytes, &mut pos, key_len)?].to_vec();
+      let val_len = u64::from_le_bytes(bytes[take(&bytes, &mut pos, 8)?].try_into()?) as usize;
+      let value = bytes[take(&bytes, &mut pos, val_len)?].to_vec();
+
+      self.db.insert(key, value)?;
+      count += 1;
+    }
+
+    self.db.flush()?;
+    Ok(count)
+  }
+}
+
+/// Reconstruct a UTC reference/forecast time from a stored second count.
+fn timestamp(secs: i64) -> anyhow::Result<RefTime> {
+  use chrono::TimeZone;
+  chrono::Utc
+    .timestamp_opt(secs, 0)
+    .single()
+    .ok_or_else(|| anyhow!("invalid timestamp in store key"))
+}